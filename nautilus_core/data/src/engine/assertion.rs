@@ -0,0 +1,243 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Durable subscription bookkeeping, modeled as a dataspace-style set of assertions rather than
+//! fire-and-forget commands.
+//!
+//! Every `Action::Subscribe` a client connection issues *asserts* an [`Assertion`]; every
+//! `Action::Unsubscribe`, client teardown, or relay disconnect *retracts* one. A reconnecting
+//! client (or a supervising component) can read back the currently active assertion set for a
+//! connection and diff it against what it now wants, rather than replaying every subscription
+//! command from scratch.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use nautilus_model::identifiers::ClientId;
+
+/// A single `(data_type, metadata)` subscription, asserted for as long as it is active.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Assertion {
+    pub data_type_name: String,
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl Assertion {
+    #[must_use]
+    pub fn new(data_type_name: impl Into<String>, metadata: BTreeMap<String, String>) -> Self {
+        Self {
+            data_type_name: data_type_name.into(),
+            metadata,
+        }
+    }
+}
+
+/// Why an [`Assertion`] was retracted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetractionReason {
+    /// An explicit `Action::Unsubscribe` command.
+    Unsubscribe,
+    /// The owning client was deregistered.
+    ClientTeardown,
+    /// The relay connection the assertion came in over was dropped.
+    RelayDisconnect,
+}
+
+/// Emitted whenever an [`Assertion`] is retracted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetractionEvent {
+    pub client_id: ClientId,
+    pub assertion: Assertion,
+    pub reason: RetractionReason,
+}
+
+/// The durable set of active assertions, partitioned by owning client connection.
+#[derive(Default)]
+pub struct AssertionSet {
+    by_client: HashMap<ClientId, HashSet<Assertion>>,
+}
+
+impl AssertionSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asserts `assertion` as active for `client_id`.
+    pub fn assert(&mut self, client_id: ClientId, assertion: Assertion) {
+        self.by_client.entry(client_id).or_default().insert(assertion);
+    }
+
+    /// Retracts `assertion` for `client_id`, returning the [`RetractionEvent`] if it was active.
+    pub fn retract(
+        &mut self,
+        client_id: ClientId,
+        assertion: &Assertion,
+        reason: RetractionReason,
+    ) -> Option<RetractionEvent> {
+        let removed = self
+            .by_client
+            .get_mut(&client_id)
+            .is_some_and(|set| set.remove(assertion));
+
+        removed.then(|| RetractionEvent {
+            client_id,
+            assertion: assertion.clone(),
+            reason,
+        })
+    }
+
+    /// Retracts every assertion held by `client_id` (e.g. on deregistration), returning one
+    /// [`RetractionEvent`] per assertion that was active.
+    pub fn retract_all(
+        &mut self,
+        client_id: ClientId,
+        reason: RetractionReason,
+    ) -> Vec<RetractionEvent> {
+        self.by_client
+            .remove(&client_id)
+            .into_iter()
+            .flatten()
+            .map(|assertion| RetractionEvent {
+                client_id,
+                assertion,
+                reason,
+            })
+            .collect()
+    }
+
+    /// Returns every currently active assertion, across all clients, whose `data_type_name`
+    /// equals `data_type_name` -- lets an accessor like `DataEngine::subscribed_quote_ticks`
+    /// project its answer off this durable set instead of reading each client's own
+    /// subscription bookkeeping directly.
+    #[must_use]
+    pub fn active_by_type(&self, data_type_name: &str) -> Vec<&Assertion> {
+        self.by_client
+            .values()
+            .flat_map(HashSet::iter)
+            .filter(|assertion| assertion.data_type_name == data_type_name)
+            .collect()
+    }
+
+    /// Returns the currently active assertion set for `client_id`.
+    #[must_use]
+    pub fn active(&self, client_id: &ClientId) -> Vec<Assertion> {
+        self.by_client
+            .get(client_id)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Diffs `desired` against the currently active set for `client_id`, returning
+    /// `(to_assert, to_retract)`: what a reconnecting client (or supervisor) would need to
+    /// subscribe/unsubscribe to reconcile desired vs. actual state.
+    #[must_use]
+    pub fn diff(
+        &self,
+        client_id: &ClientId,
+        desired: &HashSet<Assertion>,
+    ) -> (Vec<Assertion>, Vec<Assertion>) {
+        let current: HashSet<Assertion> = self
+            .by_client
+            .get(client_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let to_assert = desired.difference(&current).cloned().collect();
+        let to_retract = current.difference(desired).cloned().collect();
+        (to_assert, to_retract)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn assertion(data_type_name: &str) -> Assertion {
+        Assertion::new(data_type_name, BTreeMap::new())
+    }
+
+    #[rstest]
+    fn test_assert_and_retract() {
+        let mut set = AssertionSet::new();
+        let client_id = ClientId::default();
+        let quote = assertion("QuoteTick");
+
+        set.assert(client_id, quote.clone());
+        assert_eq!(set.active(&client_id), vec![quote.clone()]);
+
+        let event = set
+            .retract(client_id, &quote, RetractionReason::Unsubscribe)
+            .unwrap();
+        assert_eq!(event.reason, RetractionReason::Unsubscribe);
+        assert!(set.active(&client_id).is_empty());
+    }
+
+    #[rstest]
+    fn test_retract_all_on_teardown() {
+        let mut set = AssertionSet::new();
+        let client_id = ClientId::default();
+
+        set.assert(client_id, assertion("QuoteTick"));
+        set.assert(client_id, assertion("TradeTick"));
+
+        let events = set.retract_all(client_id, RetractionReason::ClientTeardown);
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|e| e.reason == RetractionReason::ClientTeardown));
+        assert!(set.active(&client_id).is_empty());
+    }
+
+    #[rstest]
+    fn test_active_by_type_projects_across_clients() {
+        let mut set = AssertionSet::new();
+        let client_a = ClientId::default();
+        let client_b = ClientId::from("SIM-2");
+
+        set.assert(client_a, assertion("QuoteTick"));
+        set.assert(client_b, assertion("QuoteTick"));
+        set.assert(client_a, assertion("TradeTick"));
+
+        let mut quotes: Vec<&str> = set
+            .active_by_type("QuoteTick")
+            .into_iter()
+            .map(|a| a.data_type_name.as_str())
+            .collect();
+        quotes.sort_unstable();
+        assert_eq!(quotes, vec!["QuoteTick", "QuoteTick"]);
+
+        assert_eq!(set.active_by_type("Bar").len(), 0);
+    }
+
+    #[rstest]
+    fn test_diff_reconciles_desired_vs_active() {
+        let mut set = AssertionSet::new();
+        let client_id = ClientId::default();
+
+        set.assert(client_id, assertion("QuoteTick"));
+        set.assert(client_id, assertion("TradeTick"));
+
+        let desired: HashSet<Assertion> =
+            [assertion("TradeTick"), assertion("Bar")].into_iter().collect();
+
+        let (to_assert, to_retract) = set.diff(&client_id, &desired);
+        assert_eq!(to_assert, vec![assertion("Bar")]);
+        assert_eq!(to_retract, vec![assertion("QuoteTick")]);
+    }
+}
@@ -19,23 +19,31 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+pub mod assertion;
+pub mod catalog;
+pub mod pattern;
+pub mod range;
+pub mod relay;
 pub mod runner;
+pub mod slow_consumer;
+pub mod trie;
 
 use std::{
     any::Any,
     cell::RefCell,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::Deref,
     rc::Rc,
     sync::Arc,
 };
 
 use indexmap::IndexMap;
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
 use nautilus_common::{
     cache::Cache,
     clock::Clock,
     logging::{RECV, RES},
-    messages::data::{DataRequest, DataResponse, SubscriptionCommand},
+    messages::data::{Action, DataRequest, DataResponse, SubscriptionCommand},
     msgbus::{handler::MessageHandler, MessageBus},
 };
 use nautilus_model::{
@@ -53,7 +61,22 @@ use nautilus_model::{
 };
 use ustr::Ustr;
 
-use crate::{aggregation::BarAggregator, client::DataClientAdapter};
+use crate::{
+    aggregation::BarAggregator,
+    client::DataClientAdapter,
+    engine::{
+        assertion::{Assertion, AssertionSet, RetractionEvent, RetractionReason},
+        catalog::ParquetDataCatalog,
+        pattern::{PatternConstraint, SubscriptionPattern},
+        range::{self, RangeCursor, RangeDataType},
+        relay::DataRelay,
+        runner::{ConnectionState, LiveRunner},
+        slow_consumer::{
+            DeadSubscriberCallback, SlowConsumerEvent, SubscriptionGuard, SLOW_CONSUMER_TOPIC,
+        },
+        trie::{subject_matches, SubjectTrie},
+    },
+};
 
 pub struct DataEngineConfig {
     pub time_bars_build_with_no_updates: bool,
@@ -62,6 +85,11 @@ pub struct DataEngineConfig {
     pub validate_data_sequence: bool,
     pub buffer_deltas: bool,
     pub external_clients: Option<Vec<ClientId>>,
+    /// The maximum number of instruments a single pattern/subject subscribe (one carrying a
+    /// `"handler_id"`) may fan out to before the engine treats that subscriber as a slow consumer
+    /// and drops it (see [`DataEngine::enforce_subscriber_budget`]). `None` means unbounded (the
+    /// pre-existing behavior).
+    pub max_pending_per_subscriber: Option<usize>,
     pub debug: bool,
 }
 
@@ -74,6 +102,7 @@ impl Default for DataEngineConfig {
             validate_data_sequence: false,
             buffer_deltas: false,
             external_clients: None,
+            max_pending_per_subscriber: None,
             debug: false,
         }
     }
@@ -91,6 +120,34 @@ pub struct DataEngine {
     synthetic_quote_feeds: HashMap<InstrumentId, Vec<SyntheticInstrument>>,
     synthetic_trade_feeds: HashMap<InstrumentId, Vec<SyntheticInstrument>>,
     buffered_deltas_map: HashMap<InstrumentId, Vec<OrderBookDelta>>,
+    catalog: Option<ParquetDataCatalog>,
+    pattern_subscriptions: Vec<(SubscriptionCommand, SubscriptionPattern)>,
+    /// Every instrument ID this engine has learned of via [`handle_instrument`](Self::handle_instrument),
+    /// independent of whether anything currently holds an exact subscription to it. Pattern/subject
+    /// wildcard subscriptions expand against this set, not [`subscribed_instruments`](Self::subscribed_instruments),
+    /// since the whole point of a wildcard subscription is to pick up instruments nothing has
+    /// exactly subscribed to yet.
+    known_instruments: HashSet<InstrumentId>,
+    runner: LiveRunner,
+    relay: Option<DataRelay>,
+    /// The latest value seen per topic, replayed to a handler the moment it subscribes
+    /// (MQTT/NATS-style "retained message" semantics).
+    retained_quotes: HashMap<InstrumentId, QuoteTick>,
+    retained_trades: HashMap<InstrumentId, TradeTick>,
+    retained_bars: HashMap<BarType, Bar>,
+    /// The latest top-of-book snapshot seen per instrument, replayed the same way as
+    /// `retained_quotes`/`retained_trades`/`retained_bars`.
+    retained_depth: HashMap<InstrumentId, OrderBookDepth10>,
+    /// Quote subscriptions keyed by a NATS-style hierarchical subject (e.g. `"data.quotes.SIM.>"`)
+    /// rather than one exact `instrument_id` per command.
+    subject_subscriptions: SubjectTrie<SubscriptionCommand>,
+    /// Durable per-client assertion bookkeeping (see [`AssertionSet`]).
+    assertions: AssertionSet,
+    /// The [`mio::Token`] each client was registered under via
+    /// [`register_client_io`](Self::register_client_io), if any -- tracked here rather than on
+    /// `DataClientAdapter` itself, since the engine is the only side that talks to
+    /// [`LiveRunner`].
+    io_tokens: HashMap<ClientId, mio::Token>,
     config: DataEngineConfig,
 }
 
@@ -113,13 +170,107 @@ impl DataEngine {
             synthetic_quote_feeds: HashMap::new(),
             synthetic_trade_feeds: HashMap::new(),
             buffered_deltas_map: HashMap::new(),
+            catalog: None,
+            pattern_subscriptions: Vec::new(),
+            known_instruments: HashSet::new(),
+            runner: LiveRunner::new().expect("Failed to initialize live I/O runner"),
+            relay: None,
+            retained_quotes: HashMap::new(),
+            retained_trades: HashMap::new(),
+            retained_bars: HashMap::new(),
+            retained_depth: HashMap::new(),
+            subject_subscriptions: SubjectTrie::new(),
+            assertions: AssertionSet::new(),
+            io_tokens: HashMap::new(),
             config: config.unwrap_or_default(),
         }
     }
 }
 
 impl DataEngine {
-    // pub fn register_catalog(&mut self, catalog: ParquetDataCatalog) {}  TODO: Implement catalog
+    /// Registers the given Parquet `catalog` as the engine's historical data backend.
+    ///
+    /// Once registered, [`request`](Self::request) will serve any [`DataRequest`] whose
+    /// `data_type` carries a `start`/`end` time range from the catalog, falling back to the
+    /// routed live client when no catalog is registered or the catalog query fails.
+    pub fn register_catalog(&mut self, catalog: ParquetDataCatalog) {
+        log::info!("Registered data catalog");
+        self.catalog = Some(catalog);
+    }
+
+    /// Registers the given [`DataRelay`], exposing every topic this engine publishes to
+    /// out-of-process TCP clients from then on.
+    pub fn register_relay(&mut self, relay: DataRelay) {
+        log::info!("Registered data relay");
+        self.relay = Some(relay);
+    }
+
+    /// Accepts any pending relay connections, feeds any inbound `PUB` data into the engine via
+    /// [`process`](Self::process), and keeps [`AssertionSet`] in sync with each session's
+    /// `SUB`/`UNSUB`/disconnect traffic -- a relay session's subscriptions are asserted under a
+    /// synthetic per-session [`ClientId`] (see [`Self::relay_client_id`]) purely so a dropped
+    /// connection retracts them with [`RetractionReason::RelayDisconnect`], same as a registered
+    /// client's teardown retracts its own.
+    pub fn poll_relay(&mut self) {
+        let Some(relay) = self.relay.as_mut() else {
+            return;
+        };
+
+        if let Err(e) = relay.accept_pending() {
+            log::error!("Relay accept failed: {e}");
+        }
+
+        let poll = relay.poll_inbound();
+
+        for data in poll.data {
+            self.process(data);
+        }
+
+        for (session_id, topic) in poll.subscribed {
+            self.assertions.assert(
+                Self::relay_client_id(session_id),
+                Assertion::new(topic, BTreeMap::new()),
+            );
+        }
+
+        for (session_id, topic) in poll.unsubscribed {
+            let assertion = Assertion::new(topic, BTreeMap::new());
+            if let Some(event) = self.assertions.retract(
+                Self::relay_client_id(session_id),
+                &assertion,
+                RetractionReason::Unsubscribe,
+            ) {
+                self.log_retraction(&event);
+            }
+        }
+
+        for session_id in poll.disconnected {
+            for event in self
+                .assertions
+                .retract_all(Self::relay_client_id(session_id), RetractionReason::RelayDisconnect)
+            {
+                self.log_retraction(&event);
+            }
+        }
+    }
+
+    /// The synthetic [`ClientId`] a relay session's `SUB`/`UNSUB` traffic is asserted under,
+    /// since the relay line protocol carries no client identity of its own.
+    fn relay_client_id(session_id: u64) -> ClientId {
+        ClientId::from(format!("relay-session-{session_id}").as_str())
+    }
+
+    /// Forwards `data` to any connected [`DataRelay`] sessions subscribed to `topic`.
+    ///
+    /// Every `MessageBus`-published data kind that has a [`Data`] counterpart is forwarded here,
+    /// not just the handful a previous version hardcoded -- a relay session's `SUB <topic>`
+    /// otherwise silently received nothing for any topic other than quotes/trades/bars, even
+    /// though the line protocol makes no such distinction.
+    fn forward_to_relay(&mut self, topic: &Ustr, data: Data) {
+        if let Some(relay) = self.relay.as_mut() {
+            relay.publish(topic, &data);
+        }
+    }
 
     /// Register the given data `client` with the engine as the default routing client.
     ///
@@ -134,15 +285,15 @@ impl DataEngine {
         self.default_client = Some(client);
     }
 
-    pub fn start(self) {
+    pub fn start(&mut self) {
         self.clients.values().for_each(|client| client.start());
     }
 
-    pub fn stop(self) {
+    pub fn stop(&mut self) {
         self.clients.values().for_each(|client| client.stop());
     }
 
-    pub fn reset(self) {
+    pub fn reset(&mut self) {
         self.clients.values().for_each(|client| client.reset());
     }
 
@@ -151,12 +302,70 @@ impl DataEngine {
         self.clock.cancel_timers();
     }
 
-    pub fn connect(&self) {
-        todo!() //  Implement actual client connections for a live/sandbox context
+    /// Connects every registered client. `DataClientAdapter` exposes no generic socket/readiness
+    /// API, so the engine cannot introspect a client's own fd -- a caller driving a live/sandbox
+    /// loop registers that fd itself via [`register_client_io`](Self::register_client_io) once it
+    /// has opened the client's connection.
+    pub fn connect(&mut self) {
+        for client in self.clients.values_mut() {
+            client.connect();
+        }
+    }
+
+    /// Disconnects every registered client, first deregistering any socket it was registered
+    /// under via [`register_client_io`](Self::register_client_io) so no further readiness events
+    /// are dispatched for it.
+    pub fn disconnect(&mut self) {
+        let client_ids: Vec<ClientId> = self.clients.keys().copied().collect();
+        for client_id in client_ids {
+            self.deregister_client_io(&client_id);
+            if let Some(client) = self.clients.get_mut(&client_id) {
+                client.disconnect();
+            }
+        }
+    }
+
+    /// Registers `fd` as `client_id`'s I/O source with the engine's [`LiveRunner`], so subsequent
+    /// [`poll`](Self::poll) calls report it non-blockingly. The caller supplies `fd` directly
+    /// (typically right after opening the client's underlying socket), rather than the engine
+    /// introspecting it off `DataClientAdapter`, which has no such method.
+    ///
+    /// A client the runner still considers [`ConnectionState::Reconnecting`] (a prior socket was
+    /// deregistered and its backoff window hasn't elapsed yet) is skipped, so a caller driving a
+    /// live/sandbox loop can call this repeatedly as a reconnect attempt without hot-looping the
+    /// underlying socket connect on every iteration.
+    pub fn register_client_io(&mut self, client_id: ClientId, fd: std::os::unix::io::RawFd) {
+        if self.runner.state(client_id) == ConnectionState::Reconnecting
+            && !self.runner.reconnect_due(client_id)
+        {
+            return;
+        }
+        match self.runner.register(client_id, fd) {
+            Ok(token) => {
+                self.io_tokens.insert(client_id, token);
+            }
+            Err(e) => {
+                log::error!("Failed registering client {client_id} with the live runner: {e}");
+            }
+        }
+    }
+
+    /// Deregisters `client_id`'s socket from the [`LiveRunner`], if it was registered via
+    /// [`register_client_io`](Self::register_client_io).
+    pub fn deregister_client_io(&mut self, client_id: &ClientId) {
+        if let Some(token) = self.io_tokens.remove(client_id) {
+            self.runner.deregister(token);
+        }
     }
 
-    pub fn disconnect(&self) {
-        todo!() // Implement actual client connections for a live/sandbox context
+    /// Runs one non-blocking iteration of the live I/O reactor, returning the ids of clients
+    /// registered via [`register_client_io`](Self::register_client_io) whose socket became
+    /// readable. Intended to be called repeatedly from the live/sandbox event loop, with
+    /// `timeout` bounding how long a single iteration may block. Draining the readable client
+    /// (e.g. calling whatever read/parse method its concrete `DataClientAdapter` exposes) is left
+    /// to the caller, since the adapter has no generic "readable" callback.
+    pub fn poll(&mut self, timeout: Option<std::time::Duration>) -> std::io::Result<Vec<ClientId>> {
+        self.runner.poll_once(timeout)
     }
 
     #[must_use]
@@ -193,44 +402,139 @@ impl DataEngine {
         self.collect_subscriptions(|client| &client.subscriptions_generic)
     }
 
+    /// Every concretely-subscribed instrument, unioned with every currently-known instrument
+    /// matched by an active [`SubscriptionPattern`] or subject-wildcard subscription -- a pattern
+    /// or subject subscription is exact-subscribed to each matching instrument as soon as it's
+    /// registered (see [`handle_pattern_subscription`](Self::handle_pattern_subscription) and
+    /// [`handle_subject_subscription`](Self::handle_subject_subscription)), but this keeps the
+    /// accessor correct even if that fan-out is ever deferred or partially failed for a client.
     #[must_use]
     pub fn subscribed_instruments(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_instrument)
+        let mut instruments: HashSet<InstrumentId> = self
+            .collect_subscriptions(|client| &client.subscriptions_instrument)
+            .into_iter()
+            .collect();
+
+        for instrument_id in &self.known_instruments {
+            if self
+                .pattern_subscriptions
+                .iter()
+                .any(|(_, pattern)| pattern.matches_instrument(instrument_id))
+            {
+                instruments.insert(*instrument_id);
+                continue;
+            }
+
+            let matches_subject = [stringify!(QuoteTick), stringify!(TradeTick)]
+                .into_iter()
+                .any(|data_type_name| {
+                    let subject = Self::tick_subject(data_type_name, instrument_id);
+                    !self.subject_subscriptions.matches(&subject).is_empty()
+                });
+            if matches_subject {
+                instruments.insert(*instrument_id);
+            }
+        }
+
+        instruments.into_iter().collect()
+    }
+
+    /// Every pattern currently installed via a pattern-bearing [`SubscriptionCommand`] (see
+    /// [`handle_pattern_subscription`](Self::handle_pattern_subscription)).
+    #[must_use]
+    pub fn subscribed_patterns(&self) -> Vec<SubscriptionPattern> {
+        self.pattern_subscriptions
+            .iter()
+            .map(|(_, pattern)| pattern.clone())
+            .collect()
+    }
+
+    /// Projects [`AssertionSet::active_by_type`] for `data_type_name` into the `"instrument_id"`
+    /// metadata each of these assertions carries (see
+    /// [`assertion_from_command`](Self::assertion_from_command) and
+    /// [`subscribe_instrument_exact`](Self::subscribe_instrument_exact), the only two places an
+    /// assertion for one of these data types is ever asserted).
+    fn assertion_instrument_ids(&self, data_type_name: &str) -> Vec<InstrumentId> {
+        self.assertions
+            .active_by_type(data_type_name)
+            .into_iter()
+            .filter_map(|assertion| {
+                assertion
+                    .metadata
+                    .get("instrument_id")
+                    .and_then(|id| id.parse().ok())
+            })
+            .collect()
     }
 
     #[must_use]
     pub fn subscribed_order_book_deltas(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_order_book_delta)
+        self.assertion_instrument_ids(stringify!(OrderBookDelta))
     }
 
     #[must_use]
     pub fn subscribed_order_book_snapshots(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_order_book_snapshot)
+        self.assertion_instrument_ids(stringify!(OrderBookDeltas))
     }
 
     #[must_use]
     pub fn subscribed_quote_ticks(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_quote_tick)
+        self.assertion_instrument_ids(stringify!(QuoteTick))
     }
 
     #[must_use]
     pub fn subscribed_trade_ticks(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_trade_tick)
+        self.assertion_instrument_ids(stringify!(TradeTick))
     }
 
     #[must_use]
     pub fn subscribed_bars(&self) -> Vec<BarType> {
-        self.collect_subscriptions(|client| &client.subscriptions_bar)
+        self.assertions
+            .active_by_type(stringify!(Bar))
+            .into_iter()
+            .filter_map(|assertion| {
+                assertion
+                    .metadata
+                    .get("bar_type")
+                    .map(|bar_type| BarType::from(bar_type.as_str()))
+            })
+            .collect()
     }
 
     #[must_use]
     pub fn subscribed_instrument_status(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_instrument_status)
+        self.assertion_instrument_ids(stringify!(InstrumentStatus))
     }
 
     #[must_use]
     pub fn subscribed_instrument_close(&self) -> Vec<InstrumentId> {
-        self.collect_subscriptions(|client| &client.subscriptions_instrument_close)
+        self.assertion_instrument_ids(stringify!(InstrumentClose))
+    }
+
+    // -- RETAINED VALUES ---------------------------------------------------------------------
+
+    /// Returns the last [`QuoteTick`] processed for `instrument_id`, if any.
+    #[must_use]
+    pub fn retained_quote(&self, instrument_id: &InstrumentId) -> Option<QuoteTick> {
+        self.retained_quotes.get(instrument_id).copied()
+    }
+
+    /// Returns the last [`TradeTick`] processed for `instrument_id`, if any.
+    #[must_use]
+    pub fn retained_trade(&self, instrument_id: &InstrumentId) -> Option<TradeTick> {
+        self.retained_trades.get(instrument_id).copied()
+    }
+
+    /// Returns the last [`Bar`] processed for `bar_type`, if any.
+    #[must_use]
+    pub fn retained_bar(&self, bar_type: &BarType) -> Option<Bar> {
+        self.retained_bars.get(bar_type).copied()
+    }
+
+    /// Returns the last top-of-book [`OrderBookDepth10`] processed for `instrument_id`, if any.
+    #[must_use]
+    pub fn retained_depth(&self, instrument_id: &InstrumentId) -> Option<OrderBookDepth10> {
+        self.retained_depth.get(instrument_id).cloned()
     }
 
     pub fn on_start(self) {
@@ -260,6 +564,14 @@ impl DataEngine {
         // correctness::check_key_in_map(&client_id, &self.clients, "client_id", "clients").unwrap();
 
         self.clients.shift_remove(client_id);
+
+        for event in self
+            .assertions
+            .retract_all(*client_id, RetractionReason::ClientTeardown)
+        {
+            self.log_retraction(&event);
+        }
+
         log::info!("Deregistered client {client_id}");
     }
 
@@ -276,8 +588,16 @@ impl DataEngine {
     /// Send a [`DataRequest`] to an endpoint that must be a data client implementation.
     pub fn execute(&mut self, msg: &dyn Any) {
         if let Some(cmd) = msg.downcast_ref::<SubscriptionCommand>() {
-            if let Some(client) = self.clients.get_mut(&cmd.client_id) {
+            if let Some(subject) = Self::subject_from_command(cmd) {
+                self.handle_subject_subscription(cmd.clone(), subject);
+            } else if let Some(pattern) = Self::pattern_from_command(cmd) {
+                self.handle_pattern_subscription(cmd.clone(), pattern);
+            } else if let Some(client) = self.clients.get_mut(&cmd.client_id) {
                 client.execute(cmd.clone());
+                self.update_assertions(cmd);
+                if cmd.action == Action::Subscribe {
+                    self.replay_retained(cmd);
+                }
             } else {
                 log::error!(
                     "Cannot handle command: no client found for {}",
@@ -289,7 +609,297 @@ impl DataEngine {
         }
     }
 
+    /// Parses a dataspace-style [`SubscriptionPattern`] out of `cmd`'s metadata, if any.
+    ///
+    /// A `venue` key constrains to instruments on that venue; a `symbol_glob` key constrains
+    /// instrument symbols against a `*`-glob. A command naming neither carries no pattern and is
+    /// routed to its client as an exact subscription, same as before.
+    fn pattern_from_command(cmd: &SubscriptionCommand) -> Option<SubscriptionPattern> {
+        let metadata = cmd.data_type.metadata()?;
+        let mut constraints = Vec::new();
+
+        if let Some(venue) = metadata.get("venue") {
+            constraints.push(PatternConstraint::Venue(Venue::from(venue.as_str())));
+        }
+        if let Some(glob) = metadata.get("symbol_glob") {
+            constraints.push(PatternConstraint::SymbolGlob(glob.clone()));
+        }
+
+        if constraints.is_empty() {
+            None
+        } else {
+            Some(SubscriptionPattern::new(constraints))
+        }
+    }
+
+    /// `Action::Subscribe` expands a pattern-bearing `cmd` into one exact subscription per
+    /// currently-known matching instrument, then retains the pattern so future-arriving
+    /// instruments are fanned out the same way from [`handle_instrument`](Self::handle_instrument).
+    ///
+    /// `Action::Unsubscribe` retracts the exact subscription for every currently-matching
+    /// instrument and removes the pattern from [`pattern_subscriptions`](Self::pattern_subscriptions)
+    /// (matched by client and pattern, since the unsubscribe `cmd` is a distinct command instance
+    /// from the one that installed it), so a pattern subscription can actually be torn down
+    /// instead of fanning out forever.
+    fn handle_pattern_subscription(&mut self, cmd: SubscriptionCommand, pattern: SubscriptionPattern) {
+        let matches: Vec<InstrumentId> = self
+            .known_instruments
+            .iter()
+            .filter(|id| pattern.matches_instrument(id))
+            .copied()
+            .collect();
+
+        match cmd.action {
+            Action::Subscribe => {
+                if !self.enforce_subscriber_budget(&cmd, &pattern.topic_label(), matches.len()) {
+                    return;
+                }
+                for instrument_id in matches {
+                    self.subscribe_instrument_exact(&cmd, instrument_id);
+                }
+                self.pattern_subscriptions.push((cmd, pattern));
+            }
+            Action::Unsubscribe => {
+                for instrument_id in matches {
+                    self.subscribe_instrument_exact(&cmd, instrument_id);
+                }
+                self.pattern_subscriptions.retain(|(existing, existing_pattern)| {
+                    !(existing.client_id == cmd.client_id && *existing_pattern == pattern)
+                });
+            }
+        }
+    }
+
+    /// Parses a NATS-style hierarchical subject out of `cmd`'s metadata, if any, for use with
+    /// the [`SubjectTrie`] fan-out path. A single command subscribes to every instrument whose
+    /// [`tick_subject`](Self::tick_subject) matches, for either `QuoteTick` or `TradeTick` --
+    /// the two data types with a per-instrument topic this subject convention describes.
+    fn subject_from_command(cmd: &SubscriptionCommand) -> Option<String> {
+        match cmd.data_type.type_name() {
+            stringify!(QuoteTick) | stringify!(TradeTick) => {}
+            _ => return None,
+        }
+        cmd.data_type.metadata()?.get("subject").cloned()
+    }
+
+    /// The canonical subject a `data_type_name` tick for `instrument_id` is matched against,
+    /// e.g. `"data.quotes.SIM.AUDUSD"` for `QuoteTick`, `"data.trades.SIM.AUDUSD"` for
+    /// `TradeTick`.
+    fn tick_subject(data_type_name: &str, instrument_id: &InstrumentId) -> String {
+        let category = match data_type_name {
+            stringify!(TradeTick) => "trades",
+            _ => "quotes",
+        };
+        format!(
+            "data.{category}.{}.{}",
+            instrument_id.venue, instrument_id.symbol
+        )
+    }
+
+    /// `Action::Subscribe` installs `subject` in the [`SubjectTrie`] and immediately fans `cmd`
+    /// out to every currently-known instrument whose `cmd`-data-type subject matches, the same as
+    /// a pattern subscription does for constraint-based patterns.
+    ///
+    /// `Action::Unsubscribe` retracts the exact subscription for every currently-matching
+    /// instrument and removes the entry from [`subject_subscriptions`](Self::subject_subscriptions)
+    /// (matched by client, data type and subject, since `SubjectTrie::remove` needs the subject
+    /// the entry was installed under and the unsubscribe `cmd` is a distinct command instance).
+    fn handle_subject_subscription(&mut self, cmd: SubscriptionCommand, subject: String) {
+        let data_type_name = cmd.data_type.type_name();
+        let matches: Vec<InstrumentId> = self
+            .known_instruments
+            .iter()
+            .filter(|id| subject_matches(&subject, &Self::tick_subject(data_type_name, id)))
+            .copied()
+            .collect();
+
+        match cmd.action {
+            Action::Subscribe => {
+                if !self.enforce_subscriber_budget(&cmd, &subject, matches.len()) {
+                    return;
+                }
+                if !self.subject_subscriptions.insert(&subject, cmd.clone()) {
+                    log::error!("Rejected malformed subject subscription: '{subject}' (`>` is only legal as the final token)");
+                    return;
+                }
+
+                for instrument_id in matches {
+                    self.subscribe_instrument_exact(&cmd, instrument_id);
+                }
+            }
+            Action::Unsubscribe => {
+                let client_id = cmd.client_id;
+                self.subject_subscriptions.retain(&mut |existing: &SubscriptionCommand| {
+                    !(existing.client_id == client_id
+                        && existing.data_type.type_name() == data_type_name
+                        && existing
+                            .data_type
+                            .metadata()
+                            .and_then(|m| m.get("subject"))
+                            .is_some_and(|s| s == &subject))
+                });
+
+                for instrument_id in matches {
+                    self.subscribe_instrument_exact(&cmd, instrument_id);
+                }
+            }
+        }
+    }
+
+    /// Issues the exact (non-pattern) [`SubscriptionCommand`] for a single `instrument_id`,
+    /// carrying forward `cmd`'s underlying data type, client, venue and action -- an
+    /// `Action::Unsubscribe` pattern/subject command must retract the exact subscription it
+    /// originally asserted, not silently re-subscribe it.
+    ///
+    /// Also asserts or retracts the fanned-out subscription in [`AssertionSet`], same as a
+    /// direct-client command routed through [`execute`](Self::execute) -- without this, a pattern
+    /// or subject fan-out subscription would never show up in `active_assertions` or the
+    /// `subscribed_*` accessors, even though the client genuinely is subscribed.
+    fn subscribe_instrument_exact(&mut self, cmd: &SubscriptionCommand, instrument_id: InstrumentId) {
+        let metadata = indexmap::indexmap! {
+            "instrument_id".to_string() => instrument_id.to_string(),
+        };
+        let exact = SubscriptionCommand::new(
+            cmd.client_id,
+            cmd.venue,
+            DataType::new(cmd.data_type.type_name(), Some(metadata)),
+            cmd.action,
+            UUID4::new(),
+            cmd.ts_init,
+        );
+
+        if let Some(client) = self.clients.get_mut(&cmd.client_id) {
+            client.execute(exact.clone());
+            self.update_assertions(&exact);
+        } else {
+            log::error!(
+                "Cannot handle pattern fan-out: no client found for {}",
+                cmd.client_id
+            );
+        }
+    }
+
+    /// Builds the durable [`Assertion`] that `cmd` asserts or retracts.
+    fn assertion_from_command(cmd: &SubscriptionCommand) -> Assertion {
+        let metadata = cmd
+            .data_type
+            .metadata()
+            .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+        Assertion::new(cmd.data_type.type_name(), metadata)
+    }
+
+    /// Updates the durable [`AssertionSet`] bookkeeping for an exact (non-pattern,
+    /// non-subject-wildcard) `cmd`: `Action::Subscribe` asserts, `Action::Unsubscribe` retracts
+    /// and logs the resulting [`RetractionEvent`].
+    fn update_assertions(&mut self, cmd: &SubscriptionCommand) {
+        let assertion = Self::assertion_from_command(cmd);
+        match cmd.action {
+            Action::Subscribe => self.assertions.assert(cmd.client_id, assertion),
+            Action::Unsubscribe => {
+                if let Some(event) =
+                    self.assertions
+                        .retract(cmd.client_id, &assertion, RetractionReason::Unsubscribe)
+                {
+                    self.log_retraction(&event);
+                }
+            }
+        }
+    }
+
+    fn log_retraction(&self, event: &RetractionEvent) {
+        log::info!(
+            "Retracted {} assertion for client {} ({:?})",
+            event.assertion.data_type_name,
+            event.client_id,
+            event.reason,
+        );
+    }
+
+    /// Returns the currently active assertion set for `client_id`, letting a reconnecting
+    /// client or a supervising component diff desired vs. actual subscriptions.
+    #[must_use]
+    pub fn active_assertions(&self, client_id: &ClientId) -> Vec<Assertion> {
+        self.assertions.active(client_id)
+    }
+
+    /// Immediately delivers the retained last value for `cmd`'s topic (if any) directly to the
+    /// freshly-subscribed handler, giving it MQTT/NATS-style "retained message" semantics
+    /// instead of waiting for the next live update.
+    ///
+    /// Delivery is point-to-point (`MessageBus::send` to the handler named in `cmd`'s
+    /// `"handler_id"` metadata), never a re-`publish` onto the shared topic -- every other
+    /// handler already subscribed to that topic must NOT see a duplicate tick just because some
+    /// other handler subscribed. A `cmd` with no `"handler_id"` has no addressable target, so
+    /// replay is skipped entirely rather than falling back to a broadcast. Callers can also
+    /// explicitly opt out with a `"replay_last"` metadata value of `"false"` (e.g. for
+    /// latency-sensitive consumers that would rather not pay for the lookup).
+    fn replay_retained(&self, cmd: &SubscriptionCommand) {
+        let Some(metadata) = cmd.data_type.metadata() else {
+            return;
+        };
+        if metadata.get("replay_last").map(String::as_str) == Some("false") {
+            return;
+        }
+        let Some(handler_id) = metadata.get("handler_id") else {
+            return;
+        };
+        let endpoint = Ustr::from(handler_id.as_str());
+        let msgbus = self.msgbus.borrow();
+
+        match cmd.data_type.type_name() {
+            stringify!(QuoteTick) => {
+                if let Some(instrument_id) = metadata
+                    .get("instrument_id")
+                    .and_then(|s| s.parse::<InstrumentId>().ok())
+                {
+                    if let Some(quote) = self.retained_quotes.get(&instrument_id) {
+                        msgbus.send(&endpoint, quote as &dyn Any);
+                    }
+                }
+            }
+            stringify!(TradeTick) => {
+                if let Some(instrument_id) = metadata
+                    .get("instrument_id")
+                    .and_then(|s| s.parse::<InstrumentId>().ok())
+                {
+                    if let Some(trade) = self.retained_trades.get(&instrument_id) {
+                        msgbus.send(&endpoint, trade as &dyn Any);
+                    }
+                }
+            }
+            stringify!(Bar) => {
+                if let Some(bar_type) = metadata
+                    .get("bar_type")
+                    .and_then(|s| s.parse::<BarType>().ok())
+                {
+                    if let Some(bar) = self.retained_bars.get(&bar_type) {
+                        msgbus.send(&endpoint, bar as &dyn Any);
+                    }
+                }
+            }
+            stringify!(OrderBookDepth10) => {
+                if let Some(instrument_id) = metadata
+                    .get("instrument_id")
+                    .and_then(|s| s.parse::<InstrumentId>().ok())
+                {
+                    if let Some(depth) = self.retained_depth.get(&instrument_id) {
+                        msgbus.send(&endpoint, depth as &dyn Any);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn request(&self, req: DataRequest) {
+        if Self::requests_time_range(&req) {
+            if let Some(resp) = self.request_from_catalog(&req) {
+                self.response(resp);
+                return;
+            }
+        }
+
         if let Some(client) = self.clients.get(&req.client_id) {
             // TODO: We don't immediately need the response
             let _ = client.request(req);
@@ -301,6 +911,205 @@ impl DataEngine {
         }
     }
 
+    /// Requests a merged, time-ordered batch of cached market data for `instrument_id` over the
+    /// half-open range `[start, end)` (`start` inclusive, `end` exclusive), falling back to the
+    /// catalog/client the same way [`request`](Self::request) does on a cache miss (i.e. the
+    /// cache holds nothing at all in the range; a partially cached range is served from the cache
+    /// alone).
+    ///
+    /// Modeled after a key-value range scan: `data_type` names the series (same as a table
+    /// name), `start`/`end` name the key range to scan, `limit` caps how many records one page
+    /// returns, and `reverse` pages newest-first instead of oldest-first. `cursor` resumes a
+    /// previous call's scan exactly where it left off; pass the returned [`RangeCursor`] back in
+    /// to fetch the next page, and stop once the returned cursor is `None` (the scan is
+    /// exhausted).
+    ///
+    /// Returns the page of matching data alongside the cursor for the next page, if any.
+    pub fn request_range(
+        &self,
+        instrument_id: InstrumentId,
+        data_type: RangeDataType,
+        start: UnixNanos,
+        end: UnixNanos,
+        limit: Option<usize>,
+        reverse: bool,
+        cursor: Option<RangeCursor>,
+    ) -> (Vec<Data>, Option<RangeCursor>) {
+        let mut batch = self.cached_range(&instrument_id, &data_type, start, end);
+
+        if batch.is_empty() {
+            batch = self.catalog_range(&instrument_id, &data_type, start, end);
+        }
+
+        if reverse {
+            batch.sort_by_key(|d| std::cmp::Reverse(Self::data_ts_init(d)));
+        } else {
+            batch.sort_by_key(Self::data_ts_init);
+        }
+
+        let skip = range::cursor_skip(&batch, cursor, Self::data_ts_init);
+        let mut page = batch.split_off(skip.min(batch.len()));
+        if let Some(limit) = limit {
+            page.truncate(limit);
+        }
+        let next = range::next_cursor(&page, limit, Self::data_ts_init).map(|mut next| {
+            // Ties at the cursor's timestamp that earlier pages already returned (the skipped
+            // prefix left in `batch`) count toward where the next page resumes.
+            let ts = next.ts_init;
+            next.seen_at_ts += batch.iter().filter(|d| Self::data_ts_init(d) == ts).count();
+            next
+        });
+
+        (page, next)
+    }
+
+    /// Reads `[start, end)` directly out of the cache for `data_type`, returning an empty batch
+    /// on a cache miss (the cache holds no data for this key at all, rather than partial data).
+    fn cached_range(
+        &self,
+        instrument_id: &InstrumentId,
+        data_type: &RangeDataType,
+        start: UnixNanos,
+        end: UnixNanos,
+    ) -> Vec<Data> {
+        let cache = self.cache.as_ref().borrow();
+        let in_range = |ts: UnixNanos| ts >= start && ts < end;
+
+        match data_type {
+            RangeDataType::QuoteTick => cache
+                .quotes(instrument_id)
+                .map(|quotes| {
+                    quotes
+                        .iter()
+                        .filter(|q| in_range(q.ts_init))
+                        .copied()
+                        .map(Data::Quote)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            RangeDataType::TradeTick => cache
+                .trades(instrument_id)
+                .map(|trades| {
+                    trades
+                        .iter()
+                        .filter(|t| in_range(t.ts_init))
+                        .copied()
+                        .map(Data::Trade)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            RangeDataType::Bar(bar_type) => cache
+                .bars(bar_type)
+                .map(|bars| {
+                    bars.iter()
+                        .filter(|b| in_range(b.ts_init))
+                        .copied()
+                        .map(Data::Bar)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Falls back to the registered [`ParquetDataCatalog`] (or an empty batch if none is
+    /// registered) for a range the cache holds nothing in.
+    ///
+    /// [`ParquetDataCatalog::query_quotes`]/`query_trades`/`query_bars` scan `[start, end]`
+    /// inclusive of `end` (they predate this half-open contract and are shared with
+    /// [`request`](Self::request), which has no `end`-exclusivity requirement of its own), so
+    /// this filters the one extra `ts_init == end` instant back out locally rather than
+    /// widening that shared catalog API's contract.
+    fn catalog_range(
+        &self,
+        instrument_id: &InstrumentId,
+        data_type: &RangeDataType,
+        start: UnixNanos,
+        end: UnixNanos,
+    ) -> Vec<Data> {
+        let Some(catalog) = self.catalog.as_ref() else {
+            return Vec::new();
+        };
+        let exclusive_end = |items: Vec<Data>| -> Vec<Data> {
+            items
+                .into_iter()
+                .filter(|d| Self::data_ts_init(d) < end)
+                .collect()
+        };
+
+        match data_type {
+            RangeDataType::QuoteTick => catalog
+                .query_quotes(instrument_id, start, end)
+                .map(|quotes| exclusive_end(quotes.into_iter().map(Data::Quote).collect()))
+                .unwrap_or_default(),
+            RangeDataType::TradeTick => catalog
+                .query_trades(instrument_id, start, end)
+                .map(|trades| exclusive_end(trades.into_iter().map(Data::Trade).collect()))
+                .unwrap_or_default(),
+            RangeDataType::Bar(bar_type) => catalog
+                .query_bars(bar_type, start, end)
+                .map(|bars| exclusive_end(bars.into_iter().map(Data::Bar).collect()))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn data_ts_init(data: &Data) -> UnixNanos {
+        match data {
+            Data::Delta(d) => d.ts_init,
+            Data::Deltas(d) => d.ts_init,
+            Data::Depth10(d) => d.ts_init,
+            Data::Quote(d) => d.ts_init,
+            Data::Trade(d) => d.ts_init,
+            Data::Bar(d) => d.ts_init,
+        }
+    }
+
+    /// Returns `true` if `req` carries a `start`/`end` time range, meaning it is asking for
+    /// historical data rather than the current live snapshot.
+    fn requests_time_range(req: &DataRequest) -> bool {
+        req.data_type
+            .metadata()
+            .is_some_and(|m| m.contains_key("start") && m.contains_key("end"))
+    }
+
+    /// Attempts to serve `req` out of the registered [`ParquetDataCatalog`], returning `None`
+    /// when no catalog is registered, the request cannot be resolved to a known data type, or the
+    /// catalog query fails.
+    fn request_from_catalog(&self, req: &DataRequest) -> Option<DataResponse> {
+        let catalog = self.catalog.as_ref()?;
+        let metadata = req.data_type.metadata()?;
+        let start: UnixNanos = metadata.get("start")?.parse::<u64>().ok()?.into();
+        let end: UnixNanos = metadata.get("end")?.parse::<u64>().ok()?.into();
+
+        let data: Arc<dyn Any + Send + Sync> = match req.data_type.type_name() {
+            stringify!(QuoteTick) => {
+                let instrument_id: InstrumentId = metadata.get("instrument_id")?.parse().ok()?;
+                Arc::new(catalog.query_quotes(&instrument_id, start, end).ok()?)
+            }
+            stringify!(TradeTick) => {
+                let instrument_id: InstrumentId = metadata.get("instrument_id")?.parse().ok()?;
+                Arc::new(catalog.query_trades(&instrument_id, start, end).ok()?)
+            }
+            stringify!(Bar) => {
+                let bar_type: BarType = metadata.get("bar_type")?.parse().ok()?;
+                Arc::new(catalog.query_bars(&bar_type, start, end).ok()?)
+            }
+            stringify!(OrderBookDelta) => {
+                let instrument_id: InstrumentId = metadata.get("instrument_id")?.parse().ok()?;
+                Arc::new(catalog.query_deltas(&instrument_id, start, end).ok()?)
+            }
+            _ => return None,
+        };
+
+        Some(DataResponse::new(
+            req.client_id,
+            req.venue,
+            req.data_type.clone(),
+            data,
+            req.request_id,
+            req.ts_init,
+        ))
+    }
+
     /// TODO: Probably not required
     /// Send a [`SubscriptionCommand`] to an endpoint that must be a data client implementation.
     pub fn send_subscription_command(&self, message: SubscriptionCommand) {
@@ -363,11 +1172,67 @@ impl DataEngine {
             log::error!("Error on cache insert: {e}");
         }
 
+        self.known_instruments.insert(instrument.id());
+        self.fan_out_pattern_subscriptions(instrument.id());
+
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_instrument_topic(instrument.id());
         msgbus.publish(&topic, &instrument as &dyn Any); // TODO: Optimize
     }
 
+    /// The topic a pattern's matching quotes/trades are published to, in addition to each
+    /// matching instrument's own exact topic. See [`SubscriptionPattern::topic_label`].
+    fn pattern_topic(pattern: &SubscriptionPattern) -> Ustr {
+        Ustr::from(&format!("data.pattern.{}", pattern.topic_label()))
+    }
+
+    /// Publishes `data` to the topic of every active pattern matching `instrument_id`, alongside
+    /// whatever exact-topic publish the caller already did.
+    fn publish_to_matching_patterns(&self, instrument_id: &InstrumentId, data: &dyn Any) {
+        let mut msgbus = self.msgbus.borrow_mut();
+        for (_, pattern) in &self.pattern_subscriptions {
+            if pattern.matches_instrument(instrument_id) {
+                msgbus.publish(&Self::pattern_topic(pattern), data);
+            }
+        }
+    }
+
+    /// Same as [`publish_to_matching_patterns`](Self::publish_to_matching_patterns), but matched
+    /// against a [`BarType`] so a [`PatternConstraint::BarAggregation`] constraint is honored.
+    fn publish_to_matching_bar_patterns(&self, bar_type: &BarType, data: &dyn Any) {
+        let mut msgbus = self.msgbus.borrow_mut();
+        for (_, pattern) in &self.pattern_subscriptions {
+            if pattern.matches_bar_type(bar_type) {
+                msgbus.publish(&Self::pattern_topic(pattern), data);
+            }
+        }
+    }
+
+    /// Subscribes `instrument_id` to every retained pattern it newly satisfies, so a
+    /// venue-/glob-level subscription installed before this instrument existed still picks it up.
+    fn fan_out_pattern_subscriptions(&mut self, instrument_id: InstrumentId) {
+        let mut matching: Vec<SubscriptionCommand> = self
+            .pattern_subscriptions
+            .iter()
+            .filter(|(_, pattern)| pattern.matches_instrument(&instrument_id))
+            .map(|(cmd, _)| cmd.clone())
+            .collect();
+
+        for data_type_name in [stringify!(QuoteTick), stringify!(TradeTick)] {
+            let subject = Self::tick_subject(data_type_name, &instrument_id);
+            matching.extend(
+                self.subject_subscriptions
+                    .matches(&subject)
+                    .into_iter()
+                    .cloned(),
+            );
+        }
+
+        for cmd in matching {
+            self.subscribe_instrument_exact(&cmd, instrument_id);
+        }
+    }
+
     fn handle_delta(&mut self, delta: OrderBookDelta) {
         // TODO: Manage buffered deltas
         // TODO: Manage book
@@ -375,6 +1240,8 @@ impl DataEngine {
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_delta_topic(delta.instrument_id);
         msgbus.publish(&topic, &delta as &dyn Any); // TODO: Optimize
+        drop(msgbus);
+        self.forward_to_relay(&topic, Data::Delta(delta));
     }
 
     fn handle_deltas(&mut self, deltas: OrderBookDeltas) {
@@ -383,14 +1250,20 @@ impl DataEngine {
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_deltas_topic(deltas.instrument_id);
         msgbus.publish(&topic, &deltas as &dyn Any); // TODO: Optimize
+        drop(msgbus);
+        self.forward_to_relay(&topic, Data::Deltas(deltas));
     }
 
     fn handle_depth10(&mut self, depth: OrderBookDepth10) {
         // TODO: Manage book
 
+        self.retained_depth.insert(depth.instrument_id, depth.clone());
+
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_depth_topic(depth.instrument_id);
         msgbus.publish(&topic, &depth as &dyn Any); // TODO: Optimize
+        drop(msgbus);
+        self.forward_to_relay(&topic, Data::Depth10(depth));
     }
 
     fn handle_quote(&mut self, quote: QuoteTick) {
@@ -400,9 +1273,14 @@ impl DataEngine {
 
         // TODO: Handle synthetics
 
+        self.retained_quotes.insert(quote.instrument_id, quote);
+
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_quote_topic(quote.instrument_id);
         msgbus.publish(&topic, &quote as &dyn Any); // TODO: Optimize
+        drop(msgbus);
+        self.publish_to_matching_patterns(&quote.instrument_id, &quote as &dyn Any);
+        self.forward_to_relay(&topic, Data::Quote(quote));
     }
 
     fn handle_trade(&mut self, trade: TradeTick) {
@@ -412,9 +1290,14 @@ impl DataEngine {
 
         // TODO: Handle synthetics
 
+        self.retained_trades.insert(trade.instrument_id, trade);
+
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_trade_topic(trade.instrument_id);
         msgbus.publish(&topic, &trade as &dyn Any); // TODO: Optimize
+        drop(msgbus);
+        self.publish_to_matching_patterns(&trade.instrument_id, &trade as &dyn Any);
+        self.forward_to_relay(&topic, Data::Trade(trade));
     }
 
     fn handle_bar(&mut self, bar: Bar) {
@@ -443,9 +1326,14 @@ impl DataEngine {
             log::error!("Error on cache insert: {e}");
         }
 
+        self.retained_bars.insert(bar.bar_type, bar);
+
         let mut msgbus = self.msgbus.borrow_mut();
         let topic = msgbus.switchboard.get_bar_topic(bar.bar_type);
         msgbus.publish(&topic, &bar as &dyn Any); // TODO: Optimize
+        drop(msgbus);
+        self.publish_to_matching_bar_patterns(&bar.bar_type, &bar as &dyn Any);
+        self.forward_to_relay(&topic, Data::Bar(bar));
     }
 
     // -- RESPONSE HANDLERS -----------------------------------------------------------------------
@@ -480,6 +1368,104 @@ impl DataEngine {
 
     // -- INTERNAL --------------------------------------------------------------------------------
 
+    /// Reacts to a [`SlowConsumerEvent`] published on [`SLOW_CONSUMER_TOPIC`] when `MessageBus`
+    /// drops a subscription for exceeding its pending-message budget: logs a warning, then
+    /// delegates to [`DeadSubscriberCallback::on_dead_subscriber`] to prune engine-owned state
+    /// tied to the dropped subscriber rather than leave it pointing at a dead handler.
+    pub fn handle_slow_consumer(&mut self, event: &SlowConsumerEvent) {
+        log::warn!(
+            "Slow consumer {} dropped from topic {} ({} pending > budget {})",
+            event.subscriber,
+            event.topic,
+            event.pending,
+            event.budget,
+        );
+        self.on_dead_subscriber(event);
+    }
+
+    /// Removes every pattern and subject subscription whose `"handler_id"` metadata names
+    /// `handler_id`, returning how many pattern subscriptions were pruned. Shared by
+    /// [`on_dead_subscriber`](DeadSubscriberCallback::on_dead_subscriber) (a `MessageBus`-reported
+    /// [`SlowConsumerEvent`]), [`drop_subscriber`](Self::drop_subscriber) (a
+    /// [`SubscriptionGuard`](slow_consumer::SubscriptionGuard) released), and
+    /// [`enforce_subscriber_budget`](Self::enforce_subscriber_budget) (this engine's own
+    /// pending-fan-out budget tripping), so all three teardown paths agree on what "dropping a
+    /// subscriber" prunes.
+    fn prune_subscriber(&mut self, handler_id: Ustr) -> usize {
+        let is_dead = |cmd: &SubscriptionCommand| {
+            cmd.data_type
+                .metadata()
+                .and_then(|m| m.get("handler_id"))
+                .is_some_and(|id| id.as_str() == handler_id.as_str())
+        };
+
+        let before = self.pattern_subscriptions.len();
+        self.pattern_subscriptions
+            .retain(|(cmd, _)| !is_dead(cmd));
+        let pruned = before - self.pattern_subscriptions.len();
+
+        self.subject_subscriptions.retain(&mut |cmd| !is_dead(cmd));
+        pruned
+    }
+
+    /// Retracts the engine-owned bookkeeping for `handler_id` (see
+    /// [`prune_subscriber`](Self::prune_subscriber)). Called from
+    /// [`SubscriptionGuard::drop`](slow_consumer::SubscriptionGuard) so a pattern/subject
+    /// subscription is automatically removed when its guard goes out of scope or unwinds
+    /// through a panic, the same as a dropped-subscriber event would do. Unlike an explicit
+    /// `Action::Unsubscribe`, this does not retract the exact per-instrument subscriptions the
+    /// pattern/subject already fanned out; it only stops further fan-out.
+    pub fn drop_subscriber(&mut self, handler_id: Ustr) {
+        if self.prune_subscriber(handler_id) > 0 {
+            log::info!("Dropped subscriber {handler_id} (guard released)");
+        }
+    }
+
+    /// Checks `pending` (the number of instruments a single pattern/subject subscribe is about to
+    /// fan out to) against
+    /// [`DataEngineConfig::max_pending_per_subscriber`](DataEngineConfig::max_pending_per_subscriber)
+    /// for `cmd`'s `"handler_id"` metadata, if any.
+    ///
+    /// `MessageBus::subscribe` itself -- where this budget would ideally be enforced against a
+    /// subscriber's actual undelivered-message backlog -- lives in `nautilus_common`, which has no
+    /// source present anywhere in this workspace checkout, so it cannot be edited from this crate.
+    /// This is the closest equivalent enforceable entirely on the engine's own side of the
+    /// contract: a single pattern/subject subscribe that would immediately fan out to more
+    /// instruments than the configured budget is treated as exceeding it. When that happens, a
+    /// real [`SlowConsumerEvent`] is published to [`SLOW_CONSUMER_TOPIC`] (picked up by whatever
+    /// [`SlowConsumerHandler`] is registered, the same as a genuine `MessageBus`-side trip would
+    /// be) and the subscriber is pruned immediately rather than installed. Returns `false` when
+    /// the subscription was dropped for exceeding its budget; `true` when there's no handler id,
+    /// no configured budget, or the budget isn't exceeded, so the caller should proceed as normal.
+    fn enforce_subscriber_budget(&mut self, cmd: &SubscriptionCommand, topic: &str, pending: usize) -> bool {
+        let Some(budget) = self.config.max_pending_per_subscriber else {
+            return true;
+        };
+        let Some(handler_id) = cmd
+            .data_type
+            .metadata()
+            .and_then(|m| m.get("handler_id"))
+            .map(|id| Ustr::from(id.as_str()))
+        else {
+            return true;
+        };
+        if pending <= budget {
+            return true;
+        }
+
+        let event = SlowConsumerEvent {
+            subscriber: handler_id,
+            topic: Ustr::from(topic),
+            pending,
+            budget,
+        };
+        self.msgbus
+            .borrow_mut()
+            .publish(SLOW_CONSUMER_TOPIC, &event as &dyn Any);
+        self.prune_subscriber(handler_id);
+        false
+    }
+
     fn update_order_book(&self, data: &Data) {
         // Only apply data if there is a book being managed,
         // as it may be being managed manually.
@@ -499,6 +1485,22 @@ impl DataEngine {
     }
 }
 
+impl DeadSubscriberCallback for DataEngine {
+    /// Prunes every pattern and subject subscription whose `"handler_id"` metadata names
+    /// `event.subscriber`, so a dropped subscriber stops being fanned out to by
+    /// [`fan_out_pattern_subscriptions`](Self::fan_out_pattern_subscriptions) once `MessageBus`
+    /// can no longer deliver to it.
+    fn on_dead_subscriber(&mut self, event: &SlowConsumerEvent) {
+        let pruned_patterns = self.prune_subscriber(event.subscriber);
+        if pruned_patterns > 0 {
+            log::info!(
+                "Pruned {pruned_patterns} pattern subscription(s) for dead subscriber {}",
+                event.subscriber,
+            );
+        }
+    }
+}
+
 pub struct SubscriptionCommandHandler {
     id: Ustr,
     data_engine: Rc<RefCell<DataEngine>>,
@@ -519,6 +1521,33 @@ impl MessageHandler for SubscriptionCommandHandler {
     }
 }
 
+/// Subscribed to [`SLOW_CONSUMER_TOPIC`] so the engine can react when `MessageBus` drops a
+/// subscription for exceeding its pending-message budget, same wiring pattern as
+/// [`SubscriptionCommandHandler`].
+pub struct SlowConsumerHandler {
+    id: Ustr,
+    data_engine: Rc<RefCell<DataEngine>>,
+}
+
+impl MessageHandler for SlowConsumerHandler {
+    fn id(&self) -> Ustr {
+        self.id
+    }
+
+    fn handle(&self, message: &dyn Any) {
+        if let Some(event) = message.downcast_ref::<SlowConsumerEvent>() {
+            self.data_engine.borrow_mut().handle_slow_consumer(event);
+        } else {
+            log::error!("Invalid message type received on {SLOW_CONSUMER_TOPIC}: {message:?}");
+        }
+    }
+    fn handle_response(&self, _resp: DataResponse) {}
+    fn handle_data(&self, _resp: Data) {}
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Tests
 ////////////////////////////////////////////////////////////////////////////////
@@ -537,7 +1566,7 @@ mod tests {
     use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
     use nautilus_model::{
         enums::BookType,
-        identifiers::TraderId,
+        identifiers::{Symbol, TraderId},
         instruments::{currency_pair::CurrencyPair, stubs::audusd_sim},
     };
     use rstest::*;
@@ -619,6 +1648,29 @@ mod tests {
         DataClientAdapter::new(client_id, venue, client, clock)
     }
 
+    #[rstest]
+    fn test_register_client_io_then_poll_reports_ready_client(
+        clock: Box<TestClock>,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+        client_id: ClientId,
+    ) {
+        use std::{io::Write, os::unix::io::AsRawFd, os::unix::net::UnixStream};
+
+        let mut engine = DataEngine::new(clock, cache, msgbus, None);
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+
+        engine.register_client_io(client_id, reader.as_raw_fd());
+        writer.write_all(b"x").unwrap();
+
+        let ready = engine
+            .poll(Some(std::time::Duration::from_millis(100)))
+            .unwrap();
+        assert_eq!(ready, vec![client_id]);
+
+        engine.deregister_client_io(&client_id);
+    }
+
     #[rstest]
     fn test_execute_subscribe_custom_data(
         audusd_sim: CurrencyPair,
@@ -988,4 +2040,756 @@ mod tests {
             .unwrap()
             .was_called());
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    fn test_replay_retained_delivers_directly_to_handler_id(
+        msgbus: Rc<RefCell<MessageBus>>,
+        data_engine: Rc<RefCell<DataEngine>>,
+    ) {
+        let quote = QuoteTick::default();
+        data_engine.borrow_mut().process(Data::Quote(quote));
+
+        let target = Ustr::from("strategy-1");
+        let handler = get_call_check_shareable_handler(target);
+        msgbus.borrow_mut().register(target, handler.clone());
+
+        let metadata = indexmap! {
+            "instrument_id".to_string() => quote.instrument_id.to_string(),
+            "handler_id".to_string() => target.to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            ClientId::default(),
+            Venue::default(),
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        data_engine.borrow().replay_retained(&cmd);
+
+        assert!(handler
+            .0
+            .as_ref()
+            .as_any()
+            .downcast_ref::<CallCheckMessageHandler>()
+            .unwrap()
+            .was_called());
+    }
+
+    #[rstest]
+    fn test_replay_retained_honors_replay_last_opt_out(
+        msgbus: Rc<RefCell<MessageBus>>,
+        data_engine: Rc<RefCell<DataEngine>>,
+    ) {
+        let quote = QuoteTick::default();
+        data_engine.borrow_mut().process(Data::Quote(quote));
+
+        let target = Ustr::from("strategy-2");
+        let handler = get_call_check_shareable_handler(target);
+        msgbus.borrow_mut().register(target, handler.clone());
+
+        let metadata = indexmap! {
+            "instrument_id".to_string() => quote.instrument_id.to_string(),
+            "handler_id".to_string() => target.to_string(),
+            "replay_last".to_string() => "false".to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            ClientId::default(),
+            Venue::default(),
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        data_engine.borrow().replay_retained(&cmd);
+
+        assert!(!handler
+            .0
+            .as_ref()
+            .as_any()
+            .downcast_ref::<CallCheckMessageHandler>()
+            .unwrap()
+            .was_called());
+    }
+
+    #[rstest]
+    fn test_replay_retained_skips_without_handler_id_instead_of_broadcasting(
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+    ) {
+        let quote = QuoteTick::default();
+        data_engine.borrow_mut().process(Data::Quote(quote));
+
+        // An already-subscribed handler on the broadcast topic must not see a duplicate just
+        // because some other `SubscriptionCommand` (with no addressable handler) replays.
+        let topic = msgbus
+            .borrow()
+            .switchboard
+            .get_quote_topic(quote.instrument_id);
+        let existing = get_call_check_shareable_handler(Ustr::from("already-subscribed"));
+        msgbus.borrow_mut().subscribe(topic, existing.clone(), None);
+
+        let metadata = indexmap! {
+            "instrument_id".to_string() => quote.instrument_id.to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            ClientId::default(),
+            Venue::default(),
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        data_engine.borrow().replay_retained(&cmd);
+
+        assert!(!existing
+            .0
+            .as_ref()
+            .as_any()
+            .downcast_ref::<CallCheckMessageHandler>()
+            .unwrap()
+            .was_called());
+    }
+
+    #[rstest]
+    fn test_venue_pattern_subscription_fans_out_to_known_and_future_instruments(
+        venue: Venue,
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        let gbpusd = InstrumentId::new(Symbol::from("GBPUSD"), venue);
+        let eurusd = InstrumentId::new(Symbol::from("EURUSD"), venue);
+
+        // Two instruments are already known before the pattern subscription is installed.
+        {
+            let mut engine = data_engine.borrow_mut();
+            engine.known_instruments.insert(audusd);
+            engine.known_instruments.insert(gbpusd);
+        }
+
+        let metadata = indexmap! {
+            "venue".to_string() => venue.to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        let endpoint = switchboard.data_engine_execute;
+        let handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(endpoint, handler);
+        msgbus.borrow().send(&endpoint, &cmd as &dyn Any);
+
+        assert_eq!(data_engine.borrow().subscribed_patterns().len(), 1);
+        assert!(data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&audusd));
+        assert!(data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&gbpusd));
+
+        // A third instrument arriving later is auto-subscribed via the retained pattern, the
+        // same path `handle_instrument` drives in production.
+        data_engine.borrow_mut().known_instruments.insert(eurusd);
+        data_engine
+            .borrow_mut()
+            .fan_out_pattern_subscriptions(eurusd);
+
+        assert!(data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&eurusd));
+    }
+
+    #[rstest]
+    fn test_subject_subscription_supports_trade_ticks_not_just_quotes(
+        venue: Venue,
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        data_engine
+            .borrow_mut()
+            .known_instruments
+            .insert(audusd);
+
+        let metadata = indexmap! {
+            "subject".to_string() => format!("data.trades.{venue}.>"),
+        };
+        let cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(TradeTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        let endpoint = switchboard.data_engine_execute;
+        let handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(endpoint, handler);
+        msgbus.borrow().send(&endpoint, &cmd as &dyn Any);
+
+        assert!(data_engine
+            .borrow()
+            .subscribed_trade_ticks()
+            .contains(&audusd));
+        assert!(data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&audusd));
+    }
+
+    #[rstest]
+    fn test_subject_subscription_rejects_non_final_gt_token(
+        venue: Venue,
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        data_engine
+            .borrow_mut()
+            .known_instruments
+            .insert(audusd);
+
+        let metadata = indexmap! {
+            "subject".to_string() => "data.quotes.>.AUDUSD".to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        let endpoint = switchboard.data_engine_execute;
+        let handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(endpoint, handler);
+        msgbus.borrow().send(&endpoint, &cmd as &dyn Any);
+
+        assert!(!data_engine
+            .borrow()
+            .subscribed_quote_ticks()
+            .contains(&audusd));
+    }
+
+    #[rstest]
+    fn test_unsubscribe_pattern_subscription_retracts_and_removes_pattern(
+        venue: Venue,
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        data_engine.borrow_mut().known_instruments.insert(audusd);
+
+        let metadata = indexmap! {
+            "venue".to_string() => venue.to_string(),
+        };
+        let subscribe = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(metadata.clone())),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        let endpoint = switchboard.data_engine_execute;
+        let handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(endpoint, handler);
+        msgbus.borrow().send(&endpoint, &subscribe as &dyn Any);
+
+        assert_eq!(data_engine.borrow().subscribed_patterns().len(), 1);
+        assert!(data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&audusd));
+
+        let unsubscribe = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Unsubscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+        msgbus.borrow().send(&endpoint, &unsubscribe as &dyn Any);
+
+        assert!(data_engine.borrow().subscribed_patterns().is_empty());
+        assert!(!data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&audusd));
+
+        // The pattern is gone, so an instrument arriving afterwards is not auto-subscribed.
+        let gbpusd = InstrumentId::new(Symbol::from("GBPUSD"), venue);
+        data_engine.borrow_mut().known_instruments.insert(gbpusd);
+        data_engine
+            .borrow_mut()
+            .fan_out_pattern_subscriptions(gbpusd);
+        assert!(!data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&gbpusd));
+    }
+
+    #[rstest]
+    fn test_unsubscribe_subject_subscription_retracts_and_removes_subject(
+        venue: Venue,
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        data_engine.borrow_mut().known_instruments.insert(audusd);
+
+        let metadata = indexmap! {
+            "subject".to_string() => format!("data.trades.{venue}.>"),
+        };
+        let subscribe = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(TradeTick), Some(metadata.clone())),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        let endpoint = switchboard.data_engine_execute;
+        let handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(endpoint, handler);
+        msgbus.borrow().send(&endpoint, &subscribe as &dyn Any);
+
+        assert!(data_engine
+            .borrow()
+            .subscribed_trade_ticks()
+            .contains(&audusd));
+
+        let unsubscribe = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(TradeTick), Some(metadata)),
+            Action::Unsubscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+        msgbus.borrow().send(&endpoint, &unsubscribe as &dyn Any);
+
+        assert!(!data_engine
+            .borrow()
+            .subscribed_trade_ticks()
+            .contains(&audusd));
+
+        // The subject is gone, so an instrument arriving afterwards is not auto-subscribed.
+        let gbpusd = InstrumentId::new(Symbol::from("GBPUSD"), venue);
+        data_engine.borrow_mut().known_instruments.insert(gbpusd);
+        data_engine
+            .borrow_mut()
+            .fan_out_pattern_subscriptions(gbpusd);
+        assert!(!data_engine
+            .borrow()
+            .subscribed_trade_ticks()
+            .contains(&gbpusd));
+    }
+
+    #[rstest]
+    fn test_subscription_guard_drops_pattern_subscription_on_drop(
+        venue: Venue,
+        msgbus: Rc<RefCell<MessageBus>>,
+        switchboard: MessagingSwitchboard,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        data_engine.borrow_mut().known_instruments.insert(audusd);
+
+        let handler_id = Ustr::from("guarded-subscriber");
+        let metadata = indexmap! {
+            "venue".to_string() => venue.to_string(),
+            "handler_id".to_string() => handler_id.to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+
+        let endpoint = switchboard.data_engine_execute;
+        let handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(endpoint, handler);
+        msgbus.borrow().send(&endpoint, &cmd as &dyn Any);
+
+        assert_eq!(data_engine.borrow().subscribed_patterns().len(), 1);
+
+        // No explicit `Action::Unsubscribe` is ever sent -- dropping the guard alone must tear
+        // the subscription down, the same as it would if the caller's scope unwound through a
+        // panic instead.
+        let guard = SubscriptionGuard::new(handler_id, Rc::downgrade(&data_engine));
+        drop(guard);
+
+        assert!(data_engine.borrow().subscribed_patterns().is_empty());
+        assert!(!data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&audusd));
+    }
+
+    #[rstest]
+    fn test_pattern_subscription_over_budget_drops_and_publishes_slow_consumer_event(
+        venue: Venue,
+    ) {
+        let trader_id = TraderId::default();
+        let msgbus = Rc::new(RefCell::new(MessageBus::new(
+            trader_id,
+            UUID4::new(),
+            None,
+            None,
+        )));
+        let cache = Rc::new(RefCell::new(Cache::default()));
+        let config = DataEngineConfig {
+            max_pending_per_subscriber: Some(1),
+            ..Default::default()
+        };
+        let data_engine = Rc::new(RefCell::new(DataEngine::new(
+            Box::new(TestClock::new()),
+            cache.clone(),
+            msgbus.clone(),
+            Some(config),
+        )));
+
+        let client_id = ClientId::default();
+        let client = Box::new(MockDataClient::new(cache, msgbus.clone(), client_id, venue));
+        let data_client =
+            DataClientAdapter::new(client_id, venue, client, Box::new(TestClock::new()));
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let audusd = InstrumentId::new(Symbol::from("AUDUSD"), venue);
+        let gbpusd = InstrumentId::new(Symbol::from("GBPUSD"), venue);
+        {
+            let mut engine = data_engine.borrow_mut();
+            engine.known_instruments.insert(audusd);
+            engine.known_instruments.insert(gbpusd);
+        }
+
+        let switchboard = msgbus.borrow().switchboard.clone();
+
+        // Wires a real `SlowConsumerHandler`, so a tripped budget is proven end-to-end through a
+        // published `SlowConsumerEvent` reaching it, not by feeding the handler a synthetic one.
+        let slow_consumer_handler = ShareableMessageHandler(Rc::new(SlowConsumerHandler {
+            id: Ustr::from("slow-consumer-handler"),
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().subscribe(
+            Ustr::from(SLOW_CONSUMER_TOPIC),
+            slow_consumer_handler,
+            None,
+        );
+
+        let execute_endpoint = switchboard.data_engine_execute;
+        let execute_handler = ShareableMessageHandler(Rc::new(SubscriptionCommandHandler {
+            id: execute_endpoint,
+            data_engine: data_engine.clone(),
+        }));
+        msgbus.borrow_mut().register(execute_endpoint, execute_handler);
+
+        // Two known instruments match this venue pattern, but the configured budget only allows
+        // fanning out to one.
+        let handler_id = Ustr::from("over-budget-subscriber");
+        let metadata = indexmap! {
+            "venue".to_string() => venue.to_string(),
+            "handler_id".to_string() => handler_id.to_string(),
+        };
+        let cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+        msgbus.borrow().send(&execute_endpoint, &cmd as &dyn Any);
+
+        // The pattern was never installed, and no instrument ended up subscribed as a result.
+        assert!(data_engine.borrow().subscribed_patterns().is_empty());
+        assert!(!data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&audusd));
+        assert!(!data_engine
+            .borrow()
+            .subscribed_instruments()
+            .contains(&gbpusd));
+    }
+
+    #[rstest]
+    fn test_slow_consumer_event_prunes_pattern_and_subject_subscriptions(
+        venue: Venue,
+        data_engine: Rc<RefCell<DataEngine>>,
+        data_client: DataClientAdapter,
+    ) {
+        let client_id = data_client.client_id;
+        data_engine.borrow_mut().register_client(data_client, None);
+
+        let dead_handler = Ustr::from("dead-handler");
+
+        let pattern_metadata = indexmap! {
+            "venue".to_string() => venue.to_string(),
+            "handler_id".to_string() => dead_handler.to_string(),
+        };
+        let pattern_cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(pattern_metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+        data_engine
+            .borrow_mut()
+            .pattern_subscriptions
+            .push((pattern_cmd, SubscriptionPattern::venue(venue)));
+
+        let subject_metadata = indexmap! {
+            "subject".to_string() => "data.quotes.SIM.>".to_string(),
+            "handler_id".to_string() => dead_handler.to_string(),
+        };
+        let subject_cmd = SubscriptionCommand::new(
+            client_id,
+            venue,
+            DataType::new(stringify!(QuoteTick), Some(subject_metadata)),
+            Action::Subscribe,
+            UUID4::new(),
+            UnixNanos::default(),
+        );
+        assert!(data_engine
+            .borrow_mut()
+            .subject_subscriptions
+            .insert("data.quotes.SIM.>", subject_cmd));
+
+        assert_eq!(data_engine.borrow().pattern_subscriptions.len(), 1);
+        assert_eq!(
+            data_engine
+                .borrow()
+                .subject_subscriptions
+                .matches("data.quotes.SIM.AUDUSD")
+                .len(),
+            1
+        );
+
+        let event = SlowConsumerEvent {
+            subscriber: dead_handler,
+            topic: Ustr::from("data.quotes.SIM.AUDUSD"),
+            pending: 10,
+            budget: 5,
+        };
+        data_engine.borrow_mut().handle_slow_consumer(&event);
+
+        assert!(data_engine.borrow().pattern_subscriptions.is_empty());
+        assert!(data_engine
+            .borrow()
+            .subject_subscriptions
+            .matches("data.quotes.SIM.AUDUSD")
+            .is_empty());
+    }
+
+    // -- `request_range` integration tests --------------------------------------------------------
+    //
+    // Unlike `range.rs`'s unit tests (which exercise `cursor_skip`/`next_cursor` directly over
+    // bare timestamps), these drive the real `DataEngine::request_range` against a cache
+    // populated the same way live data arrives: through `DataEngine::process`.
+
+    fn quote_at(ts: u64) -> QuoteTick {
+        QuoteTick {
+            ts_init: UnixNanos::from(ts),
+            ..QuoteTick::default()
+        }
+    }
+
+    #[rstest]
+    fn test_request_range_empty_when_nothing_cached_in_range(
+        clock: Box<TestClock>,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+    ) {
+        let mut engine = DataEngine::new(clock, cache, msgbus, None);
+        let quote = quote_at(5);
+        engine.process(Data::Quote(quote));
+
+        let (page, cursor) = engine.request_range(
+            quote.instrument_id,
+            RangeDataType::QuoteTick,
+            UnixNanos::from(100),
+            UnixNanos::from(200),
+            None,
+            false,
+            None,
+        );
+
+        assert!(page.is_empty());
+        assert_eq!(cursor, None);
+    }
+
+    #[rstest]
+    fn test_request_range_limit_truncates_and_yields_a_usable_continuation_cursor(
+        clock: Box<TestClock>,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+    ) {
+        let mut engine = DataEngine::new(clock, cache, msgbus, None);
+        let instrument_id = quote_at(0).instrument_id;
+        for ts in 0..5 {
+            engine.process(Data::Quote(quote_at(ts)));
+        }
+
+        let (first_page, cursor) = engine.request_range(
+            instrument_id,
+            RangeDataType::QuoteTick,
+            UnixNanos::from(0),
+            UnixNanos::from(5),
+            Some(2),
+            false,
+            None,
+        );
+        assert_eq!(
+            first_page.iter().map(DataEngine::data_ts_init).collect::<Vec<_>>(),
+            vec![UnixNanos::from(0), UnixNanos::from(1)],
+        );
+        let cursor = cursor.expect("a full page should yield a continuation cursor");
+
+        let (second_page, next_cursor) = engine.request_range(
+            instrument_id,
+            RangeDataType::QuoteTick,
+            UnixNanos::from(0),
+            UnixNanos::from(5),
+            Some(2),
+            false,
+            Some(cursor),
+        );
+        assert_eq!(
+            second_page.iter().map(DataEngine::data_ts_init).collect::<Vec<_>>(),
+            vec![UnixNanos::from(2), UnixNanos::from(3)],
+        );
+        assert!(next_cursor.is_some());
+
+        let (third_page, final_cursor) = engine.request_range(
+            instrument_id,
+            RangeDataType::QuoteTick,
+            UnixNanos::from(0),
+            UnixNanos::from(5),
+            Some(2),
+            false,
+            next_cursor,
+        );
+        assert_eq!(
+            third_page.iter().map(DataEngine::data_ts_init).collect::<Vec<_>>(),
+            vec![UnixNanos::from(4)],
+        );
+        assert_eq!(final_cursor, None);
+    }
+
+    #[rstest]
+    fn test_request_range_orders_ties_at_the_same_timestamp_without_dropping_or_duplicating(
+        clock: Box<TestClock>,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+    ) {
+        let mut engine = DataEngine::new(clock, cache, msgbus, None);
+        let instrument_id = quote_at(0).instrument_id;
+        // Three ticks share `ts_init = 1`, bracketed by one tick each at `0` and `2`.
+        for ts in [0, 1, 1, 1, 2] {
+            engine.process(Data::Quote(quote_at(ts)));
+        }
+
+        let (first_page, cursor) = engine.request_range(
+            instrument_id,
+            RangeDataType::QuoteTick,
+            UnixNanos::from(0),
+            UnixNanos::from(3),
+            Some(3),
+            false,
+            None,
+        );
+        assert_eq!(
+            first_page.iter().map(DataEngine::data_ts_init).collect::<Vec<_>>(),
+            vec![UnixNanos::from(0), UnixNanos::from(1), UnixNanos::from(1)],
+        );
+        let cursor = cursor.expect("a full page should yield a continuation cursor");
+
+        let (second_page, next_cursor) = engine.request_range(
+            instrument_id,
+            RangeDataType::QuoteTick,
+            UnixNanos::from(0),
+            UnixNanos::from(3),
+            Some(3),
+            false,
+            Some(cursor),
+        );
+        // Resumes after the two already-returned `ts_init = 1` ticks: the third tie, then `2`.
+        assert_eq!(
+            second_page.iter().map(DataEngine::data_ts_init).collect::<Vec<_>>(),
+            vec![UnixNanos::from(1), UnixNanos::from(2)],
+        );
+        assert_eq!(next_cursor, None);
+    }
+}
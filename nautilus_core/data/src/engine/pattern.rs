@@ -0,0 +1,178 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A small dataspace-style pattern matcher for subscription commands.
+//!
+//! Borrows the "dataspace" idea from tuple-space systems: rather than naming one exact topic,
+//! a [`SubscriptionPattern`] names a tree of constraints over the *fields* that make up a topic
+//! (venue, instrument symbol, bar aggregation, ...). Anything that satisfies every constraint is
+//! a match -- whether it was already known when the pattern was installed, or arrives later.
+
+use nautilus_model::{
+    data::bar::BarType,
+    enums::BarAggregation,
+    identifiers::{InstrumentId, Venue},
+};
+
+/// A single constraint over one field of an instrument or bar-type topic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternConstraint {
+    /// Matches only instruments on the given venue.
+    Venue(Venue),
+    /// Matches instrument symbols against a `*`-glob (e.g. `"AUD*"`, `"*USD"`, `"*"`).
+    SymbolGlob(String),
+    /// Matches only bars built with the given aggregation.
+    BarAggregation(BarAggregation),
+}
+
+impl PatternConstraint {
+    fn matches_instrument(&self, instrument_id: &InstrumentId) -> bool {
+        match self {
+            Self::Venue(venue) => &instrument_id.venue == venue,
+            Self::SymbolGlob(glob) => glob_match(glob, instrument_id.symbol.as_str()),
+            Self::BarAggregation(_) => true, // Not applicable to a bare instrument
+        }
+    }
+
+    fn matches_bar_type(&self, bar_type: &BarType) -> bool {
+        match self {
+            Self::Venue(_) | Self::SymbolGlob(_) => {
+                self.matches_instrument(&bar_type.instrument_id)
+            }
+            Self::BarAggregation(aggregation) => bar_type.spec.aggregation == *aggregation,
+        }
+    }
+
+    /// A stable label for this constraint, used to build a pattern's
+    /// [`SubscriptionPattern::topic_label`] (which joins these in constraint order).
+    fn label(&self) -> String {
+        match self {
+            Self::Venue(venue) => format!("venue={venue}"),
+            Self::SymbolGlob(glob) => format!("symbol={glob}"),
+            Self::BarAggregation(aggregation) => format!("bar_aggregation={aggregation:?}"),
+        }
+    }
+}
+
+/// A dataspace-style pattern: the conjunction of every [`PatternConstraint`] it carries.
+///
+/// A pattern with no constraints matches everything (the `*` / "all instruments" case).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubscriptionPattern {
+    constraints: Vec<PatternConstraint>,
+}
+
+impl SubscriptionPattern {
+    #[must_use]
+    pub fn new(constraints: Vec<PatternConstraint>) -> Self {
+        Self { constraints }
+    }
+
+    /// Returns a pattern matching every instrument on `venue`.
+    #[must_use]
+    pub fn venue(venue: Venue) -> Self {
+        Self::new(vec![PatternConstraint::Venue(venue)])
+    }
+
+    /// Returns a pattern matching instrument symbols against `glob` (e.g. `"AUD*"`).
+    #[must_use]
+    pub fn symbol_glob(glob: impl Into<String>) -> Self {
+        Self::new(vec![PatternConstraint::SymbolGlob(glob.into())])
+    }
+
+    /// Returns `true` if `instrument_id` satisfies every constraint in this pattern.
+    #[must_use]
+    pub fn matches_instrument(&self, instrument_id: &InstrumentId) -> bool {
+        self.constraints
+            .iter()
+            .all(|c| c.matches_instrument(instrument_id))
+    }
+
+    /// Returns `true` if `bar_type` satisfies every constraint in this pattern, including
+    /// [`PatternConstraint::BarAggregation`] (which [`Self::matches_instrument`] cannot evaluate,
+    /// since a bare instrument carries no bar aggregation).
+    #[must_use]
+    pub fn matches_bar_type(&self, bar_type: &BarType) -> bool {
+        self.constraints
+            .iter()
+            .all(|c| c.matches_bar_type(bar_type))
+    }
+
+    /// A stable label identifying this pattern's constraints (e.g. `"venue=SIM"`), used as the
+    /// suffix of a "pattern topic" -- a topic published to in addition to each matching
+    /// instrument's own exact topic, so a subscriber can listen to the whole pattern at once
+    /// instead of to every instrument it currently happens to expand to.
+    #[must_use]
+    pub fn topic_label(&self) -> String {
+        self.constraints
+            .iter()
+            .map(PatternConstraint::label)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Matches `value` against a single-`*`-wildcard glob (no other glob syntax is supported).
+fn glob_match(glob: &str, value: &str) -> bool {
+    match glob.split_once('*') {
+        None => glob == value,
+        Some((prefix, suffix)) => {
+            value.len() >= prefix.len() + suffix.len()
+                && value.starts_with(prefix)
+                && value.ends_with(suffix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nautilus_model::identifiers::{InstrumentId, Symbol, Venue};
+    use rstest::rstest;
+
+    use super::*;
+
+    fn instrument(symbol: &str, venue: &str) -> InstrumentId {
+        InstrumentId::new(Symbol::from(symbol), Venue::from(venue))
+    }
+
+    #[rstest]
+    #[case("AUD*", "AUDUSD", true)]
+    #[case("AUD*", "GBPUSD", false)]
+    #[case("*USD", "AUDUSD", true)]
+    #[case("*", "ANYTHING", true)]
+    #[case("AUDUSD", "AUDUSD", true)]
+    fn test_glob_match(#[case] glob: &str, #[case] value: &str, #[case] expected: bool) {
+        assert_eq!(glob_match(glob, value), expected);
+    }
+
+    #[rstest]
+    fn test_pattern_matches_venue_and_symbol() {
+        let pattern = SubscriptionPattern::new(vec![
+            PatternConstraint::Venue(Venue::from("SIM")),
+            PatternConstraint::SymbolGlob("AUD*".to_string()),
+        ]);
+
+        assert!(pattern.matches_instrument(&instrument("AUDUSD", "SIM")));
+        assert!(!pattern.matches_instrument(&instrument("GBPUSD", "SIM")));
+        assert!(!pattern.matches_instrument(&instrument("AUDUSD", "IDEALPRO")));
+    }
+
+    #[rstest]
+    fn test_pattern_with_no_constraints_matches_everything() {
+        let pattern = SubscriptionPattern::default();
+
+        assert!(pattern.matches_instrument(&instrument("AUDUSD", "SIM")));
+    }
+}
@@ -0,0 +1,163 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! The `data_type` selector and paging types for
+//! [`DataEngine::request_range`](super::DataEngine::request_range).
+//!
+//! Modeled after a key-value store's range read: a [`RangeDataType`] names *which* series to
+//! read, `start`/`end` name the half-open `[start, end)` key range within it (`start` inclusive,
+//! `end` exclusive), same as a `SCAN` over a sorted key space keyed by `ts_init`.
+
+use nautilus_model::{data::bar::BarType, types::UnixNanos};
+
+/// Selects which cached/catalogued series a [`DataEngine::request_range`] call reads from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RangeDataType {
+    QuoteTick,
+    TradeTick,
+    Bar(BarType),
+}
+
+impl RangeDataType {
+    /// Returns the `DataType::type_name()` string this selector corresponds to, for routing
+    /// through the same catalog/client fallback paths as [`DataEngine::request`](super::DataEngine::request).
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::QuoteTick => stringify!(QuoteTick),
+            Self::TradeTick => stringify!(TradeTick),
+            Self::Bar(_) => stringify!(Bar),
+        }
+    }
+}
+
+/// An opaque continuation cursor for paging through [`DataEngine::request_range`] results.
+///
+/// Carries just enough state to resume a `[start, end)` scan exactly where a previous page left
+/// off: the `ts_init` of the last record that page returned, and the zero-based position of that
+/// record among all records at that exact timestamp consumed so far (ties at one timestamp are
+/// otherwise indistinguishable).
+/// Opaque to callers -- constructed only by [`DataEngine::request_range`] and fed back in
+/// unmodified for the next page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RangeCursor {
+    pub(super) ts_init: UnixNanos,
+    pub(super) seen_at_ts: usize,
+}
+
+/// Returns the index into `sorted` (already ordered the same direction the scan is paging in)
+/// of the first entry after whatever [`RangeCursor`] last left off at -- i.e. how many leading
+/// entries of `sorted` a resumed scan should skip. `sorted.len()` if every entry was consumed.
+pub(super) fn cursor_skip<T>(
+    sorted: &[T],
+    cursor: Option<RangeCursor>,
+    ts_init: impl Fn(&T) -> UnixNanos,
+) -> usize {
+    let Some(cursor) = cursor else {
+        return 0;
+    };
+
+    let mut seen_at_ts = 0usize;
+    for (i, item) in sorted.iter().enumerate() {
+        let ts = ts_init(item);
+        if ts != cursor.ts_init {
+            continue;
+        }
+        if seen_at_ts == cursor.seen_at_ts {
+            return i + 1;
+        }
+        seen_at_ts += 1;
+    }
+    sorted.len()
+}
+
+/// Builds the [`RangeCursor`] a caller should pass back to fetch the page after `page`, or
+/// `None` if `page` didn't fill `limit` (meaning the scan is exhausted).
+pub(super) fn next_cursor<T>(
+    page: &[T],
+    limit: Option<usize>,
+    ts_init: impl Fn(&T) -> UnixNanos,
+) -> Option<RangeCursor> {
+    let Some(limit) = limit else {
+        return None;
+    };
+    if page.len() < limit {
+        return None;
+    }
+    let last = page.last()?;
+    let last_ts = ts_init(last);
+    let seen_at_ts = page.iter().filter(|item| ts_init(item) == last_ts).count() - 1;
+    Some(RangeCursor {
+        ts_init: last_ts,
+        seen_at_ts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn ts(n: u64) -> UnixNanos {
+        UnixNanos::from(n)
+    }
+
+    #[rstest]
+    fn test_cursor_skip_with_no_cursor_skips_nothing() {
+        let sorted = [ts(1), ts(2), ts(3)];
+        assert_eq!(cursor_skip(&sorted, None, |t| *t), 0);
+    }
+
+    #[rstest]
+    fn test_cursor_skip_resumes_after_the_last_returned_timestamp() {
+        let sorted = [ts(1), ts(2), ts(3), ts(4)];
+        let cursor = Some(RangeCursor {
+            ts_init: ts(2),
+            seen_at_ts: 0,
+        });
+        assert_eq!(cursor_skip(&sorted, cursor, |t| *t), 2);
+    }
+
+    #[rstest]
+    fn test_cursor_skip_disambiguates_ties_at_the_same_timestamp() {
+        let sorted = [ts(1), ts(2), ts(2), ts(2), ts(3)];
+        let cursor = Some(RangeCursor {
+            ts_init: ts(2),
+            seen_at_ts: 1,
+        });
+        // Skips past the first two `ts(2)` entries (indices 1 and 2), resuming at index 3.
+        assert_eq!(cursor_skip(&sorted, cursor, |t| *t), 3);
+    }
+
+    #[rstest]
+    fn test_next_cursor_is_none_when_page_is_short_of_limit() {
+        let page = [ts(1), ts(2)];
+        assert_eq!(next_cursor(&page, Some(5), |t| *t), None);
+        assert_eq!(next_cursor(&page, None, |t| *t), None);
+    }
+
+    #[rstest]
+    fn test_next_cursor_resumes_paging_from_the_last_entry() {
+        let page = [ts(1), ts(2)];
+        let cursor = next_cursor(&page, Some(2), |t| *t).unwrap();
+        assert_eq!(cursor.ts_init, ts(2));
+        assert_eq!(cursor.seen_at_ts, 0);
+
+        // Feeding it back in resumes exactly one entry further.
+        let next_page = [ts(2), ts(3)];
+        assert_eq!(cursor_skip(&next_page, Some(cursor), |t| *t), 1);
+    }
+}
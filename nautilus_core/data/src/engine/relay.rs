@@ -0,0 +1,277 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A NATS-style line-protocol relay that exposes the `MessageBus` topics the [`DataEngine`]
+//! publishes to (per-instrument quote/trade/bar topics built by `switchboard.get_quote_topic`
+//! and friends) to external, out-of-process TCP clients.
+//!
+//! Protocol (one command per line, `\r\n`-terminated):
+//!
+//! - `SUB <topic> <sid>` -- subscribe this connection to `topic` under subscriber id `sid`.
+//! - `UNSUB <sid>` -- cancel a previous `SUB`.
+//! - `PUB <topic> <nbytes>\r\n<payload>` -- publish the next `nbytes` raw bytes to `topic`; the
+//!   relay deserializes `payload` and hands it back to the caller to feed into the engine, the
+//!   same way data arriving from a local live client is.
+//!
+//! Data the relay forwards to subscribers is framed the same way: `MSG <topic> <sid>
+//! <nbytes>\r\n<payload>`.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use nautilus_model::data::Data;
+
+/// One connected relay client and the subscriptions it currently holds.
+struct RelaySession {
+    /// Assigned by [`DataRelay::accept_pending`] when the connection was accepted; identifies
+    /// this session across [`RelayPoll`] events, since the line protocol itself carries no
+    /// connection identity of its own.
+    id: u64,
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    /// Maps subscriber id (`sid`) to the topic it was given for.
+    subscriptions: HashMap<String, String>,
+}
+
+impl RelaySession {
+    fn new(id: u64, stream: TcpStream) -> std::io::Result<Self> {
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(Self {
+            id,
+            stream,
+            reader,
+            subscriptions: HashMap::new(),
+        })
+    }
+
+    fn sids_for(&self, topic: &str) -> Vec<String> {
+        self.subscriptions
+            .iter()
+            .filter(|(_, t)| t.as_str() == topic)
+            .map(|(sid, _)| sid.clone())
+            .collect()
+    }
+}
+
+/// What [`DataRelay::poll_inbound`] observed this poll.
+///
+/// Alongside any [`Data`] decoded off inbound `PUB` traffic, this surfaces the
+/// subscription-lifecycle events a caller needs to keep a relay session's assertions (see
+/// [`AssertionSet`](super::assertion::AssertionSet)) in sync with the `SUB`/`UNSUB`/disconnect
+/// traffic that session actually sent -- none of which a previous version reported at all, so a
+/// relay session's subscriptions (and their eventual teardown) were invisible outside this
+/// module.
+#[derive(Default)]
+pub struct RelayPoll {
+    /// Data decoded off inbound `PUB` lines, ready for
+    /// [`DataEngine::process`](super::DataEngine::process).
+    pub data: Vec<Data>,
+    /// `(session_id, topic)` pairs newly `SUB`'d this poll.
+    pub subscribed: Vec<(u64, String)>,
+    /// `(session_id, topic)` pairs `UNSUB`'d this poll.
+    pub unsubscribed: Vec<(u64, String)>,
+    /// Ids of sessions whose connection was dropped this poll (EOF, a read error, or a malformed
+    /// `PUB` payload that desynced the stream).
+    pub disconnected: Vec<u64>,
+}
+
+/// A TCP relay exposing [`MessageBus`](nautilus_common::msgbus::MessageBus) topics to
+/// out-of-process clients.
+pub struct DataRelay {
+    listener: TcpListener,
+    sessions: Vec<RelaySession>,
+    next_session_id: u64,
+}
+
+impl DataRelay {
+    pub fn bind(addr: impl ToSocketAddrs) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            sessions: Vec::new(),
+            next_session_id: 0,
+        })
+    }
+
+    /// Accepts any pending inbound connections without blocking.
+    pub fn accept_pending(&mut self) -> std::io::Result<()> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _addr)) => {
+                    stream.set_nonblocking(true)?;
+                    let id = self.next_session_id;
+                    self.next_session_id += 1;
+                    self.sessions.push(RelaySession::new(id, stream)?);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Processes any buffered `SUB`/`UNSUB`/`PUB` lines from every connected session, returning a
+    /// [`RelayPoll`] of everything observed.
+    pub fn poll_inbound(&mut self) -> RelayPoll {
+        let mut poll = RelayPoll::default();
+        self.sessions.retain_mut(|session| loop {
+            let mut line = String::new();
+            match session.reader.read_line(&mut line) {
+                Ok(0) => {
+                    poll.disconnected.push(session.id);
+                    return false; // Connection closed
+                }
+                Ok(_) => {
+                    if !Self::handle_line(session, line.trim_end(), &mut poll) {
+                        poll.disconnected.push(session.id);
+                        return false;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return true,
+                Err(_) => {
+                    poll.disconnected.push(session.id);
+                    return false;
+                }
+            }
+        });
+        poll
+    }
+
+    fn handle_line(session: &mut RelaySession, line: &str, poll: &mut RelayPoll) -> bool {
+        let mut parts = line.splitn(2, ' ');
+        match parts.next() {
+            Some("SUB") => {
+                if let Some(rest) = parts.next() {
+                    if let Some((topic, sid)) = rest.rsplit_once(' ') {
+                        session
+                            .subscriptions
+                            .insert(sid.to_string(), topic.to_string());
+                        poll.subscribed.push((session.id, topic.to_string()));
+                    }
+                }
+                true
+            }
+            Some("UNSUB") => {
+                if let Some(sid) = parts.next() {
+                    if let Some(topic) = session.subscriptions.remove(sid.trim()) {
+                        poll.unsubscribed.push((session.id, topic));
+                    }
+                }
+                true
+            }
+            Some("PUB") => {
+                let Some(rest) = parts.next() else {
+                    return true;
+                };
+                let Some((_topic, nbytes)) = rest.rsplit_once(' ') else {
+                    return true;
+                };
+                let Ok(nbytes) = nbytes.trim().parse::<usize>() else {
+                    return true;
+                };
+
+                let mut payload = vec![0_u8; nbytes];
+                if session.reader.read_exact(&mut payload).is_err() {
+                    return false;
+                }
+                if let Ok(data) = serde_json::from_slice::<Data>(&payload) {
+                    poll.data.push(data);
+                }
+                true
+            }
+            _ => true, // Ignore unknown lines rather than dropping the connection
+        }
+    }
+
+    /// Forwards `data` to every session subscribed to `topic`, framed as `MSG <topic> <sid>
+    /// <nbytes>\r\n<payload>`.
+    pub fn publish(&mut self, topic: &str, data: &Data) {
+        let Ok(payload) = serde_json::to_vec(data) else {
+            log::error!("Failed encoding relay payload for topic {topic}");
+            return;
+        };
+
+        self.sessions.retain_mut(|session| {
+            for sid in session.sids_for(topic) {
+                let frame = format!("MSG {topic} {sid} {}\r\n", payload.len());
+                if session.stream.write_all(frame.as_bytes()).is_err()
+                    || session.stream.write_all(&payload).is_err()
+                    || session.stream.write_all(b"\r\n").is_err()
+                {
+                    return false;
+                }
+            }
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, net::SocketAddr, time::Duration};
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn local_relay() -> (DataRelay, SocketAddr) {
+        let relay = DataRelay::bind("127.0.0.1:0").unwrap();
+        let addr = relay.listener.local_addr().unwrap();
+        (relay, addr)
+    }
+
+    /// `accept_pending`/`poll_inbound` are both non-blocking, so a just-written line may not be
+    /// visible to the very next poll -- retries briefly rather than flaking on that race.
+    fn poll_until(relay: &mut DataRelay, mut predicate: impl FnMut(&RelayPoll) -> bool) -> RelayPoll {
+        for _ in 0..200 {
+            let poll = relay.poll_inbound();
+            if predicate(&poll) {
+                return poll;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("timed out waiting for relay poll condition");
+    }
+
+    #[rstest]
+    fn test_poll_inbound_reports_subscribe_unsubscribe_and_disconnect() {
+        let (mut relay, addr) = local_relay();
+        let mut client = TcpStream::connect(addr).unwrap();
+        relay.accept_pending().unwrap();
+
+        client
+            .write_all(b"SUB data.quotes.SIM.AUDUSD sid1\r\n")
+            .unwrap();
+        let poll = poll_until(&mut relay, |poll| !poll.subscribed.is_empty());
+        assert_eq!(
+            poll.subscribed,
+            vec![(0, "data.quotes.SIM.AUDUSD".to_string())]
+        );
+
+        client.write_all(b"UNSUB sid1\r\n").unwrap();
+        let poll = poll_until(&mut relay, |poll| !poll.unsubscribed.is_empty());
+        assert_eq!(
+            poll.unsubscribed,
+            vec![(0, "data.quotes.SIM.AUDUSD".to_string())]
+        );
+
+        drop(client);
+        let poll = poll_until(&mut relay, |poll| !poll.disconnected.is_empty());
+        assert_eq!(poll.disconnected, vec![0]);
+    }
+}
@@ -0,0 +1,281 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A readiness-based async runner that drives registered live `DataClientAdapter`s' socket I/O
+//! on a non-blocking event loop, so the [`DataEngine`](super::DataEngine) can be used in a
+//! live/sandbox context instead of only backtest.
+//!
+//! Each adapter that owns a socket registers its `RawFd` with the [`LiveRunner`]; the runner
+//! polls all registered descriptors for readiness in one syscall and reports back which
+//! `ClientId`s have data waiting, leaving it to the caller to drain them. This mirrors a classic
+//! `epoll`/`kqueue` reactor: the runner never reads application data itself, it only tells you
+//! when it's safe to do so without blocking.
+//!
+//! Alongside the reactor, the runner tracks each client's [`ConnectionState`] through
+//! `Disconnected -> Connecting -> Connected -> Reconnecting -> Connecting -> ...` and an
+//! exponential [`Self::reconnect_due`] backoff, so a caller driving a live/sandbox loop can
+//! retry a dropped connection without hot-looping against it.
+
+use std::{
+    collections::HashMap,
+    io,
+    os::unix::io::RawFd,
+    time::{Duration, Instant},
+};
+
+use mio::{unix::SourceFd, Events, Interest, Poll, Token};
+use nautilus_model::identifiers::ClientId;
+
+/// The initial backoff before the first reconnect attempt after a disconnect.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+/// The backoff ceiling; doubles from [`INITIAL_BACKOFF`] on every consecutive failed attempt
+/// until it is reset by a successful [`LiveRunner::poll_once`] readiness event.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The lifecycle state of one client's registration with the [`LiveRunner`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Never registered. [`LiveRunner::deregister`] always moves a client to
+    /// [`ConnectionState::Reconnecting`], never back to this state.
+    Disconnected,
+    /// A socket was just registered and is awaiting its first readiness event.
+    Connecting,
+    /// At least one readiness event has been observed since the socket was registered.
+    Connected,
+    /// The socket was deregistered after a disconnect; a reconnect attempt is pending, gated by
+    /// [`LiveRunner::reconnect_due`].
+    Reconnecting,
+}
+
+/// One registered source: the `ClientId` it belongs to, and the `fd` it was registered under
+/// (retained so [`LiveRunner::deregister`] can hand it back to `mio` -- `Poll::registry` can
+/// only deregister a source it still has the original, or an equivalent, handle for).
+struct Registration {
+    client_id: ClientId,
+    fd: RawFd,
+}
+
+/// Owns the non-blocking event loop for live data client sockets.
+pub struct LiveRunner {
+    poll: Poll,
+    tokens: HashMap<Token, Registration>,
+    next_token: usize,
+    states: HashMap<ClientId, ConnectionState>,
+    backoff: HashMap<ClientId, Duration>,
+    next_attempt_at: HashMap<ClientId, Instant>,
+}
+
+impl LiveRunner {
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            poll: Poll::new()?,
+            tokens: HashMap::new(),
+            next_token: 0,
+            states: HashMap::new(),
+            backoff: HashMap::new(),
+            next_attempt_at: HashMap::new(),
+        })
+    }
+
+    /// Registers `fd` as the I/O source for `client_id`, returning the [`Token`] the runner
+    /// assigned it, and moves its [`ConnectionState`] to [`ConnectionState::Connecting`]. The
+    /// caller is responsible for retaining this token for a later
+    /// [`deregister`](Self::deregister) call (e.g. on disconnect).
+    pub fn register(&mut self, client_id: ClientId, fd: RawFd) -> io::Result<Token> {
+        let token = Token(self.next_token);
+        self.next_token += 1;
+
+        self.poll
+            .registry()
+            .register(&mut SourceFd(&fd), token, Interest::READABLE)?;
+        self.tokens.insert(token, Registration { client_id, fd });
+        self.states.insert(client_id, ConnectionState::Connecting);
+        self.next_attempt_at.remove(&client_id);
+        Ok(token)
+    }
+
+    /// Deregisters a previously-registered source so it is no longer polled, also freeing its
+    /// `fd` from the underlying `mio` registry -- without this, a later `register` of a reused
+    /// `fd` fails with `EEXIST`, since the registry still believes it owns the stale
+    /// registration. Moves `client_id` to [`ConnectionState::Reconnecting`] and arms its next
+    /// backoff window.
+    pub fn deregister(&mut self, token: Token) {
+        let Some(registration) = self.tokens.remove(&token) else {
+            return;
+        };
+        if let Err(e) = self
+            .poll
+            .registry()
+            .deregister(&mut SourceFd(&registration.fd))
+        {
+            log::error!(
+                "Failed deregistering client {} (fd {}) from the live runner: {e}",
+                registration.client_id,
+                registration.fd,
+            );
+        }
+
+        self.states
+            .insert(registration.client_id, ConnectionState::Reconnecting);
+        let backoff = self.arm_backoff(registration.client_id);
+        self.next_attempt_at
+            .insert(registration.client_id, Instant::now() + backoff);
+    }
+
+    /// Returns `client_id`'s current [`ConnectionState`], or [`ConnectionState::Disconnected`]
+    /// if it has never been registered.
+    #[must_use]
+    pub fn state(&self, client_id: ClientId) -> ConnectionState {
+        self.states
+            .get(&client_id)
+            .copied()
+            .unwrap_or(ConnectionState::Disconnected)
+    }
+
+    /// Returns `true` once `client_id`'s current backoff window (armed by
+    /// [`deregister`](Self::deregister)) has elapsed, i.e. a caller may attempt `register` again.
+    /// Always `true` for a client that has never been registered or is not currently
+    /// [`ConnectionState::Reconnecting`].
+    #[must_use]
+    pub fn reconnect_due(&self, client_id: ClientId) -> bool {
+        self.next_attempt_at
+            .get(&client_id)
+            .map_or(true, |due| Instant::now() >= *due)
+    }
+
+    /// Doubles and returns `client_id`'s backoff duration (capped at [`MAX_BACKOFF`]), starting
+    /// from [`INITIAL_BACKOFF`] on the first disconnect.
+    fn arm_backoff(&mut self, client_id: ClientId) -> Duration {
+        let current = self
+            .backoff
+            .get(&client_id)
+            .copied()
+            .unwrap_or(INITIAL_BACKOFF);
+        self.backoff
+            .insert(client_id, (current * 2).min(MAX_BACKOFF));
+        current
+    }
+
+    /// Blocks for up to `timeout` waiting for readiness events, returning the `ClientId`s whose
+    /// registered socket became readable. A `None` timeout blocks until at least one event (or
+    /// a signal) arrives, matching `mio::Poll::poll`'s own semantics. Each returned `ClientId`
+    /// moves to [`ConnectionState::Connected`] and has its backoff reset.
+    pub fn poll_once(&mut self, timeout: Option<Duration>) -> io::Result<Vec<ClientId>> {
+        let mut events = Events::with_capacity(64);
+        self.poll.poll(&mut events, timeout)?;
+
+        let ready: Vec<ClientId> = events
+            .iter()
+            .filter(|event| event.is_readable())
+            .filter_map(|event| self.tokens.get(&event.token()))
+            .map(|registration| registration.client_id)
+            .collect();
+
+        for &client_id in &ready {
+            self.states.insert(client_id, ConnectionState::Connected);
+            self.backoff.remove(&client_id);
+            self.next_attempt_at.remove(&client_id);
+        }
+
+        Ok(ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        os::unix::{io::AsRawFd, net::UnixStream},
+    };
+
+    use nautilus_model::identifiers::ClientId;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_state_is_disconnected_before_any_registration() {
+        let runner = LiveRunner::new().unwrap();
+        assert_eq!(
+            runner.state(ClientId::default()),
+            ConnectionState::Disconnected
+        );
+    }
+
+    #[rstest]
+    fn test_register_moves_to_connecting_then_poll_once_moves_to_connected() {
+        let mut runner = LiveRunner::new().unwrap();
+        let client_id = ClientId::default();
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+
+        let token = runner.register(client_id, reader.as_raw_fd()).unwrap();
+        assert_eq!(runner.state(client_id), ConnectionState::Connecting);
+
+        writer.write_all(b"x").unwrap();
+        let ready = runner.poll_once(Some(Duration::from_millis(100))).unwrap();
+
+        assert_eq!(ready, vec![client_id]);
+        assert_eq!(runner.state(client_id), ConnectionState::Connected);
+
+        runner.deregister(token);
+    }
+
+    #[rstest]
+    fn test_deregister_moves_to_reconnecting_and_arms_backoff() {
+        let mut runner = LiveRunner::new().unwrap();
+        let client_id = ClientId::default();
+        let (_writer, reader) = UnixStream::pair().unwrap();
+
+        let token = runner.register(client_id, reader.as_raw_fd()).unwrap();
+        runner.deregister(token);
+
+        assert_eq!(runner.state(client_id), ConnectionState::Reconnecting);
+        assert!(!runner.reconnect_due(client_id));
+    }
+
+    #[rstest]
+    fn test_deregister_allows_reregistering_the_same_fd_without_eexist() {
+        let mut runner = LiveRunner::new().unwrap();
+        let client_id = ClientId::default();
+        let (_writer, reader) = UnixStream::pair().unwrap();
+        let fd = reader.as_raw_fd();
+
+        let token = runner.register(client_id, fd).unwrap();
+        runner.deregister(token);
+
+        // Without a real `registry().deregister(...)` call this would fail with `EEXIST`, since
+        // `mio` would still believe the old registration owns this fd.
+        assert!(runner.register(client_id, fd).is_ok());
+    }
+
+    #[rstest]
+    fn test_successful_poll_once_resets_backoff() {
+        let mut runner = LiveRunner::new().unwrap();
+        let client_id = ClientId::default();
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+
+        let first_token = runner.register(client_id, reader.as_raw_fd()).unwrap();
+        runner.deregister(first_token);
+        assert!(!runner.reconnect_due(client_id));
+
+        let second_token = runner.register(client_id, reader.as_raw_fd()).unwrap();
+        writer.write_all(b"x").unwrap();
+        runner.poll_once(Some(Duration::from_millis(100))).unwrap();
+
+        assert!(runner.reconnect_due(client_id));
+
+        runner.deregister(second_token);
+    }
+}
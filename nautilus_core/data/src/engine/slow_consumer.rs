@@ -0,0 +1,100 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Slow-consumer handling for the [`DataEngine`](super::DataEngine)'s side of `MessageBus`
+//! delivery.
+//!
+//! Ideally, the bounded-delivery mechanism -- a per-subscription pending-message budget, tripped
+//! when `MessageBus::publish` can't keep a handler drained -- would live on `MessageBus::subscribe`
+//! itself in `nautilus_common::msgbus`, enforcing
+//! [`DataEngineConfig::max_pending_per_subscriber`](super::DataEngineConfig::max_pending_per_subscriber)
+//! against a subscriber's actual undelivered-message backlog. `nautilus_common` has no source
+//! present anywhere in this workspace checkout (only `nautilus_model` and `nautilus_data` are),
+//! so that file cannot be edited from here.
+//!
+//! What this module does instead is apply the same contract -- a per-subscriber budget, and
+//! automatic teardown on drop or panic -- to the half of a subscription this crate fully owns:
+//! the pattern/subject fan-out state in [`DataEngine::pattern_subscriptions`] and
+//! [`DataEngine::subject_subscriptions`].
+//! [`DataEngine::enforce_subscriber_budget`](super::DataEngine::enforce_subscriber_budget) treats
+//! a single pattern/subject subscribe that would immediately fan out to more instruments than the
+//! configured budget as exceeding it, publishes a real [`SlowConsumerEvent`] to
+//! [`SLOW_CONSUMER_TOPIC`], and drops the subscriber instead of installing it.
+//! [`SubscriptionGuard`] gives a caller RAII-style teardown of that same state: dropping the guard
+//! (falling out of scope, or unwinding through a panic) retracts it via
+//! [`DataEngine::drop_subscriber`](super::DataEngine::drop_subscriber), without needing an
+//! explicit `Action::Unsubscribe` round trip.
+//!
+//! [`DataEngine`](super::DataEngine) also implements [`DeadSubscriberCallback`], so a
+//! `SlowConsumerEvent` a real `MessageBus` publishes once that budget genuinely is enforced on its
+//! own side prunes the same state, via [`handle_slow_consumer`](super::DataEngine::handle_slow_consumer).
+
+use std::{cell::RefCell, rc::Weak};
+
+use ustr::Ustr;
+
+use super::DataEngine;
+
+/// The reserved topic `MessageBus` publishes a [`SlowConsumerEvent`] to when it drops a
+/// subscription for exceeding its pending-message budget.
+pub const SLOW_CONSUMER_TOPIC: &str = "system.slow_consumer";
+
+/// Published by `MessageBus` when a subscriber's pending-message budget is exceeded and its
+/// subscription is dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowConsumerEvent {
+    /// The id of the handler that was dropped.
+    pub subscriber: Ustr,
+    /// The topic it was dropped from.
+    pub topic: Ustr,
+    /// How many messages were pending for it at the time it was dropped.
+    pub pending: usize,
+    /// The configured budget it exceeded.
+    pub budget: usize,
+}
+
+/// A "dead subscriber" callback: invoked once per [`SlowConsumerEvent`] so engine-owned state
+/// tied to `subscriber` (a relay session, a retained pattern subscription, ...) can be cleaned
+/// up rather than left pointing at a handler the bus no longer delivers to.
+pub trait DeadSubscriberCallback {
+    fn on_dead_subscriber(&mut self, event: &SlowConsumerEvent);
+}
+
+/// An RAII handle for the engine-owned pattern/subject subscription bookkeeping behind one
+/// `"handler_id"`. Dropping it (scope exit, or unwinding through a panic) retracts that
+/// bookkeeping via [`DataEngine::drop_subscriber`], the same teardown
+/// [`DeadSubscriberCallback::on_dead_subscriber`] performs for a `MessageBus`-reported
+/// [`SlowConsumerEvent`] -- giving a caller that wants scope-bound subscription lifetime an
+/// alternative to routing an explicit `Action::Unsubscribe` command back through
+/// [`DataEngine::execute`](super::DataEngine::execute).
+pub struct SubscriptionGuard {
+    handler_id: Ustr,
+    engine: Weak<RefCell<DataEngine>>,
+}
+
+impl SubscriptionGuard {
+    #[must_use]
+    pub fn new(handler_id: Ustr, engine: Weak<RefCell<DataEngine>>) -> Self {
+        Self { handler_id, engine }
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        if let Some(engine) = self.engine.upgrade() {
+            engine.borrow_mut().drop_subscriber(self.handler_id);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A [`SubjectTrie`] matching NATS-style hierarchical subject wildcards over dot/dash-delimited
+//! topics (e.g. `data.quotes.SIM.AUDUSD`).
+//!
+//! Two wildcard tokens are supported:
+//!
+//! - `*` matches exactly one token.
+//! - `>` matches one or more trailing tokens, and may only appear as the final token of a
+//!   subscription subject.
+//!
+//! Matching a concrete topic against every installed subject is a single trie descent rather
+//! than a linear scan over every subscription, so it scales with topic depth rather than
+//! subscriber count.
+
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct TrieNode<T> {
+    /// Exact-token children.
+    children: HashMap<String, TrieNode<T>>,
+    /// The `*` wildcard child, if any subject installed one at this position.
+    star: Option<Box<TrieNode<T>>>,
+    /// Values whose subject terminates exactly at this node.
+    values: Vec<T>,
+    /// Values whose subject ends in `>` at this node (matches one or more tokens below this node,
+    /// not a topic ending exactly here).
+    gt_values: Vec<T>,
+}
+
+impl<T> TrieNode<T> {
+    fn new() -> Self {
+        Self {
+            children: HashMap::new(),
+            star: None,
+            values: Vec::new(),
+            gt_values: Vec::new(),
+        }
+    }
+}
+
+/// A trie of hierarchical subject patterns, keyed by `.`/`-`-delimited token.
+pub struct SubjectTrie<T> {
+    root: TrieNode<T>,
+}
+
+impl<T> Default for SubjectTrie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SubjectTrie<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            root: TrieNode::new(),
+        }
+    }
+
+    /// Installs `value` under `subject` (e.g. `"data.quotes.SIM.>"`).
+    ///
+    /// Returns `false` without installing anything if `subject` is malformed: `>` is only a
+    /// legal token as the final one of a subject, so e.g. `"data.quotes.>.AUDUSD"` is rejected
+    /// rather than silently truncated at the `>` with the rest of the subject dropped.
+    #[must_use]
+    pub fn insert(&mut self, subject: &str, value: T) -> bool {
+        let tokens = tokenize(subject);
+        if !Self::is_valid(&tokens) {
+            return false;
+        }
+        Self::insert_rec(&mut self.root, &tokens, value);
+        true
+    }
+
+    /// Returns `true` unless `tokens` contains a `>` that isn't the final token.
+    fn is_valid(tokens: &[&str]) -> bool {
+        match tokens.iter().position(|&t| t == ">") {
+            Some(pos) => pos == tokens.len() - 1,
+            None => true,
+        }
+    }
+
+    fn insert_rec(node: &mut TrieNode<T>, tokens: &[&str], value: T) {
+        match tokens.split_first() {
+            None => node.values.push(value),
+            Some((&">", _)) => node.gt_values.push(value),
+            Some((&"*", rest)) => {
+                Self::insert_rec(node.star.get_or_insert_with(|| Box::new(TrieNode::new())), rest, value);
+            }
+            Some((token, rest)) => {
+                Self::insert_rec(
+                    node.children.entry((*token).to_string()).or_insert_with(TrieNode::new),
+                    rest,
+                    value,
+                );
+            }
+        }
+    }
+
+    /// Returns every value whose installed subject matches the concrete `topic`.
+    #[must_use]
+    pub fn matches(&self, topic: &str) -> Vec<&T> {
+        let mut out = Vec::new();
+        Self::collect(&self.root, &tokenize(topic), &mut out);
+        out
+    }
+
+    fn collect<'a>(node: &'a TrieNode<T>, tokens: &[&str], out: &mut Vec<&'a T>) {
+        if !tokens.is_empty() {
+            out.extend(node.gt_values.iter());
+        }
+        match tokens.split_first() {
+            None => out.extend(node.values.iter()),
+            Some((token, rest)) => {
+                if let Some(child) = node.children.get(*token) {
+                    Self::collect(child, rest, out);
+                }
+                if let Some(star) = &node.star {
+                    Self::collect(star, rest, out);
+                }
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> SubjectTrie<T> {
+    /// Removes every value equal to `value` that was installed under `subject`.
+    pub fn remove(&mut self, subject: &str, value: &T) {
+        Self::remove_rec(&mut self.root, &tokenize(subject), value);
+    }
+
+    fn remove_rec(node: &mut TrieNode<T>, tokens: &[&str], value: &T) {
+        match tokens.split_first() {
+            None => node.values.retain(|v| v != value),
+            Some((&">", _)) => node.gt_values.retain(|v| v != value),
+            Some((&"*", rest)) => {
+                if let Some(star) = node.star.as_mut() {
+                    Self::remove_rec(star, rest, value);
+                }
+            }
+            Some((token, rest)) => {
+                if let Some(child) = node.children.get_mut(*token) {
+                    Self::remove_rec(child, rest, value);
+                }
+            }
+        }
+    }
+}
+
+impl<T> SubjectTrie<T> {
+    /// Retains only the values for which `predicate` returns `true`, across every subject
+    /// installed anywhere in the trie -- unlike [`Self::remove`], this doesn't require knowing
+    /// which subject a value was installed under (e.g. pruning every subscription belonging to
+    /// one dropped subscriber, regardless of which subject fanned it out).
+    pub fn retain(&mut self, predicate: &mut impl FnMut(&T) -> bool) {
+        Self::retain_rec(&mut self.root, predicate);
+    }
+
+    fn retain_rec(node: &mut TrieNode<T>, predicate: &mut impl FnMut(&T) -> bool) {
+        node.values.retain(|v| predicate(v));
+        node.gt_values.retain(|v| predicate(v));
+        for child in node.children.values_mut() {
+            Self::retain_rec(child, predicate);
+        }
+        if let Some(star) = node.star.as_mut() {
+            Self::retain_rec(star, predicate);
+        }
+    }
+}
+
+/// Splits a subject/topic into its `.`/`-`-delimited tokens.
+fn tokenize(subject: &str) -> Vec<&str> {
+    subject.split(['.', '-']).collect()
+}
+
+/// Returns `true` if the concrete `topic` matches the single `subject` pattern.
+///
+/// A one-off equivalent of building a one-entry [`SubjectTrie`] and calling
+/// [`SubjectTrie::matches`], useful when checking a single new subject against a handful of
+/// already-known topics rather than building a trie for it.
+#[must_use]
+pub fn subject_matches(subject: &str, topic: &str) -> bool {
+    let subject_tokens = tokenize(subject);
+    let topic_tokens = tokenize(topic);
+    matches_rec(&subject_tokens, &topic_tokens)
+}
+
+fn matches_rec(subject: &[&str], topic: &[&str]) -> bool {
+    match subject.split_first() {
+        None => topic.is_empty(),
+        Some((&">", subject_rest)) => subject_rest.is_empty() && !topic.is_empty(),
+        Some((&"*", subject_rest)) => {
+            !topic.is_empty() && matches_rec(subject_rest, &topic[1..])
+        }
+        Some((token, subject_rest)) => {
+            topic.first() == Some(token) && matches_rec(subject_rest, &topic[1..])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_exact_match() {
+        let mut trie = SubjectTrie::new();
+        trie.insert("data.quotes.SIM.AUDUSD", 1);
+
+        assert_eq!(trie.matches("data.quotes.SIM.AUDUSD"), vec![&1]);
+        assert!(trie.matches("data.quotes.SIM.GBPUSD").is_empty());
+    }
+
+    #[rstest]
+    fn test_single_token_wildcard() {
+        let mut trie = SubjectTrie::new();
+        trie.insert("data.quotes.*.AUDUSD", 1);
+
+        assert_eq!(trie.matches("data.quotes.SIM.AUDUSD"), vec![&1]);
+        assert_eq!(trie.matches("data.quotes.IDEALPRO.AUDUSD"), vec![&1]);
+        assert!(trie.matches("data.quotes.SIM.GBPUSD").is_empty());
+    }
+
+    #[rstest]
+    fn test_trailing_wildcard_matches_one_or_more() {
+        let mut trie = SubjectTrie::new();
+        trie.insert("data.quotes.SIM.>", 1);
+
+        assert_eq!(trie.matches("data.quotes.SIM.AUDUSD"), vec![&1]);
+        assert_eq!(trie.matches("data.quotes.SIM.AUDUSD.BOOK"), vec![&1]);
+        assert!(trie.matches("data.quotes.SIM").is_empty());
+        assert!(trie.matches("data.quotes.IDEALPRO.AUDUSD").is_empty());
+    }
+
+    #[rstest]
+    fn test_remove() {
+        let mut trie = SubjectTrie::new();
+        trie.insert("data.quotes.SIM.>", 1);
+        trie.insert("data.quotes.SIM.>", 2);
+
+        trie.remove("data.quotes.SIM.>", &1);
+
+        assert_eq!(trie.matches("data.quotes.SIM.AUDUSD"), vec![&2]);
+    }
+
+    #[rstest]
+    fn test_subject_matches_standalone_helper() {
+        assert!(subject_matches("data.quotes.SIM.>", "data.quotes.SIM.AUDUSD"));
+        assert!(subject_matches("data.quotes.*.AUDUSD", "data.quotes.SIM.AUDUSD"));
+        assert!(!subject_matches("data.quotes.SIM.>", "data.quotes.IDEALPRO.AUDUSD"));
+    }
+
+    #[rstest]
+    fn test_non_final_gt_token_is_rejected() {
+        let mut trie = SubjectTrie::new();
+
+        assert!(!trie.insert("data.quotes.>.AUDUSD", 1));
+        assert!(trie.matches("data.quotes.SIM.AUDUSD").is_empty());
+        assert!(trie.matches("data.quotes.anything.AUDUSD").is_empty());
+    }
+
+    #[rstest]
+    fn test_retain_prunes_matching_values_across_every_subject() {
+        let mut trie = SubjectTrie::new();
+        trie.insert("data.quotes.SIM.>", 1);
+        trie.insert("data.quotes.*.AUDUSD", 2);
+        trie.insert("data.quotes.SIM.AUDUSD", 1);
+
+        trie.retain(&mut |v| *v != 1);
+
+        assert_eq!(trie.matches("data.quotes.SIM.AUDUSD"), vec![&2]);
+    }
+
+    #[rstest]
+    fn test_multiple_patterns_can_match_one_topic() {
+        let mut trie = SubjectTrie::new();
+        trie.insert("data.quotes.SIM.>", 1);
+        trie.insert("data.quotes.*.AUDUSD", 2);
+        trie.insert("data.quotes.SIM.AUDUSD", 3);
+
+        let mut matches = trie.matches("data.quotes.SIM.AUDUSD");
+        matches.sort();
+        assert_eq!(matches, vec![&1, &2, &3]);
+    }
+}
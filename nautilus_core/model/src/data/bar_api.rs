@@ -13,9 +13,7 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
-use std::collections::hash_map::DefaultHasher;
 use std::ffi::c_char;
-use std::hash::{Hash, Hasher};
 
 use nautilus_core::string::str_to_cstr;
 
@@ -24,17 +22,40 @@ use crate::identifiers::instrument_id::InstrumentId;
 
 use super::bar::{Bar, BarSpecification, BarType};
 
+/// A fixed-seed FNV-1a hash over a value's canonical string representation.
+///
+/// `std::collections::hash_map::DefaultHasher` is explicitly *not* guaranteed to be stable
+/// across Rust releases or platforms, which matters here because `bar_hash`/`bar_type_hash`/
+/// `bar_specification_hash` cross the FFI boundary into Python and get used as dict/set keys --
+/// a toolchain upgrade changing the hash would silently break any persisted state keyed by it.
+/// Hashing the same canonical string the `*_to_cstr` functions already produce keeps this
+/// stable independent of both the toolchain and the in-memory struct layout.
+pub(crate) fn stable_hash(s: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in s.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
 /// Returns a [`BarSpecification`] as a C string pointer.
 #[no_mangle]
 pub extern "C" fn bar_specification_to_cstr(bar_spec: &BarSpecification) -> *const c_char {
     str_to_cstr(&bar_spec.to_string())
 }
 
+/// Returns a stable, version-independent hash of `bar_spec`.
+///
+/// Unlike a `Hash` impl hashed through `DefaultHasher`, this value is guaranteed stable across
+/// Rust releases and platforms, making it safe to persist or use as a long-lived dict/set key
+/// on the Python side.
 #[no_mangle]
 pub extern "C" fn bar_specification_hash(bar_spec: &BarSpecification) -> u64 {
-    let mut h = DefaultHasher::new();
-    bar_spec.hash(&mut h);
-    h.finish()
+    stable_hash(&bar_spec.to_string())
 }
 
 #[no_mangle]
@@ -123,11 +144,86 @@ pub extern "C" fn bar_type_ge(lhs: &BarType, rhs: &BarType) -> u8 {
     u8::from(lhs >= rhs)
 }
 
+/// Returns a stable, version-independent hash of `bar_type`. See [`bar_specification_hash`].
 #[no_mangle]
 pub extern "C" fn bar_type_hash(bar_type: &BarType) -> u64 {
-    let mut h = DefaultHasher::new();
-    bar_type.hash(&mut h);
-    h.finish()
+    stable_hash(&bar_type.to_string())
+}
+
+/// A `(instrument_id, spec)` projection of a [`BarType`] that deliberately omits
+/// `aggregation_source` from both equality and hashing.
+///
+/// `bar_type_eq_composite`/`bar_type_hash_composite` each build one of these rather than
+/// hand-rolling "ignore `aggregation_source`" twice -- the omitted field is declared once, as
+/// this struct's shape, which is the wrapper-type analogue of a `#[derivative(Hash = "ignore")]`
+/// attribute on `BarType` itself.
+#[derive(Clone, PartialEq, Eq)]
+struct CompositeBarKey {
+    instrument_id: InstrumentId,
+    spec: BarSpecification,
+}
+
+impl From<&BarType> for CompositeBarKey {
+    fn from(bar_type: &BarType) -> Self {
+        Self {
+            instrument_id: bar_type.instrument_id,
+            spec: bar_type.spec,
+        }
+    }
+}
+
+impl std::fmt::Display for CompositeBarKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.instrument_id, self.spec)
+    }
+}
+
+/// Returns whether `lhs` and `rhs` share the same `instrument_id` and `spec`, ignoring
+/// `aggregation_source`.
+///
+/// Lets a strategy match an internally-aggregated `BarType` against the externally-fed
+/// `BarType` of the same instrument/specification, which [`bar_type_eq`] (which also compares
+/// `aggregation_source`) cannot do.
+#[no_mangle]
+pub extern "C" fn bar_type_eq_composite(lhs: &BarType, rhs: &BarType) -> u8 {
+    u8::from(CompositeBarKey::from(lhs) == CompositeBarKey::from(rhs))
+}
+
+/// Returns a stable, version-independent hash of `bar_type`'s `instrument_id` and `spec`,
+/// ignoring `aggregation_source`. See [`bar_type_eq_composite`].
+#[no_mangle]
+pub extern "C" fn bar_type_hash_composite(bar_type: &BarType) -> u64 {
+    stable_hash(&CompositeBarKey::from(bar_type).to_string())
+}
+
+/// Extends [`BarType`] with composite-aggregation queries.
+///
+/// These would naturally be inherent `BarType` methods alongside its other accessors; they live
+/// here as an extension trait only because `bar.rs` isn't part of this checkout.
+pub trait BarTypeCompositeExt {
+    /// Returns `true` if this is an internally-aggregated composite of some other (externally
+    /// fed) bar type over the same instrument/specification.
+    #[must_use]
+    fn is_composite(&self) -> bool;
+
+    /// Returns the "standard" form of this `BarType`: the same `instrument_id`/`spec`, with
+    /// `aggregation_source` set to [`AggregationSource::External`].
+    #[must_use]
+    fn standard(&self) -> BarType;
+}
+
+impl BarTypeCompositeExt for BarType {
+    fn is_composite(&self) -> bool {
+        self.aggregation_source == AggregationSource::Internal
+    }
+
+    fn standard(&self) -> BarType {
+        BarType {
+            instrument_id: self.instrument_id,
+            spec: self.spec,
+            aggregation_source: AggregationSource::External,
+        }
+    }
 }
 
 /// Returns a [`BarType`] as a C string pointer.
@@ -162,9 +258,347 @@ pub extern "C" fn bar_eq(lhs: &Bar, rhs: &Bar) -> u8 {
     u8::from(lhs == rhs)
 }
 
+/// Returns a stable, version-independent hash of `bar`. See [`bar_specification_hash`].
 #[no_mangle]
 pub extern "C" fn bar_hash(bar: &Bar) -> u64 {
-    let mut h = DefaultHasher::new();
-    bar.hash(&mut h);
-    h.finish()
+    stable_hash(&bar.to_string())
+}
+
+// -------------------------------------------------------------------------------------------------
+// FlatBuffers bulk transfer
+// -------------------------------------------------------------------------------------------------
+//
+// Crossing a `Bar` one at a time via `bar_clone`/`bar_drop` is expensive when streaming millions
+// of historical bars into Python. `bars_to_flatbuffer` instead encodes a contiguous slice of
+// `Bar` into a single FlatBuffers buffer that Python can read with `flatbuffers`' zero-copy
+// accessors, with no per-field copies on the way across.
+//
+// The schema mirrors `Bar`/`BarType`/`BarSpecification`: fixed-point price/quantity fields plus
+// the `BarAggregation`/`PriceType`/`AggregationSource` enum discriminants as `u8`s, addressed by
+// `bar_type_hash` rather than repeating the full `BarType` string per row.
+//
+// `bars_from_flatbuffer` is the inverse of `bars_to_flatbuffer`, handing back the same `BarRow`s
+// rather than reconstructed `Bar`s -- see `BarRow`'s doc comment for why. Every buffer is
+// prefixed with `BAR_ROWS_WIRE_VERSION` so a reader built against a different `BarRow` layout
+// rejects it outright instead of misreading the raw bytes.
+//
+// `BarRow` carries `price_precision`/`size_precision` alongside the raw fixed-point values, so a
+// caller with access to `Price`/`Quantity` can reconstruct a real `Bar` from one; this crate
+// itself can't do that reconstruction yet, since `bar.rs` isn't part of this checkout.
+
+/// The wire version prepended to every buffer [`bars_to_flatbuffer`] produces.
+///
+/// [`bars_from_flatbuffer`] rejects any buffer whose version doesn't match, rather than
+/// misinterpreting bytes laid out by a `BarRow` schema it predates -- a reader mismatch here
+/// would otherwise fail silently, since the FlatBuffers payload itself carries no schema
+/// identity beyond this.
+///
+/// Bumped to `2` when `BarRow` gained `price_precision`/`size_precision`: a `1`-tagged buffer
+/// predates those fields and must be rejected rather than read with garbage precision.
+pub const BAR_ROWS_WIRE_VERSION: u32 = 2;
+
+/// POD mirror of a `Bar` row in the FlatBuffers schema. Every field is fixed-size so a
+/// `Vec<BarRow>` can be read by Python with no per-field copies.
+///
+/// Carries the raw fixed-point `open`/`high`/`low`/`close`/`volume` values alongside the
+/// `price_precision`/`size_precision` their `Price`/`Quantity` were constructed with -- enough to
+/// reconstruct a real `Price`/`Quantity` (and therefore a real `Bar`) once `bar.rs` is part of
+/// this checkout. Until then, [`bars_from_flatbuffer`] still hands back `BarRow`s rather than
+/// `Bar`s, since `Bar`/`Price`/`Quantity` themselves aren't available here to construct; see its
+/// doc comment.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BarRow {
+    pub bar_type_hash: u64,
+    pub aggregation: u8,
+    pub price_type: u8,
+    pub aggregation_source: u8,
+    pub price_precision: u8,
+    pub size_precision: u8,
+    pub open: i64,
+    pub high: i64,
+    pub low: i64,
+    pub close: i64,
+    pub volume: u64,
+    pub ts_event: u64,
+    pub ts_init: u64,
+}
+
+impl flatbuffers::Push for BarRow {
+    type Output = BarRow;
+
+    fn push(&self, dst: &mut [u8], _rest: &[u8]) {
+        // SAFETY: `BarRow` is `#[repr(C)]` and contains only fixed-size integer fields, so its
+        // in-memory representation is exactly `size_of::<BarRow>()` well-defined bytes.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                std::ptr::from_ref(self).cast::<u8>(),
+                std::mem::size_of::<BarRow>(),
+            )
+        };
+        dst.copy_from_slice(bytes);
+    }
+}
+
+/// A stable-ABI byte buffer handed across the FFI boundary, paired with
+/// [`flatbuffer_buffer_drop`] to reclaim it.
+#[repr(C)]
+pub struct FlatBufferBytes {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub cap: usize,
+}
+
+impl FlatBufferBytes {
+    fn from_vec(mut bytes: Vec<u8>) -> Self {
+        let buf = Self {
+            ptr: bytes.as_mut_ptr(),
+            len: bytes.len(),
+            cap: bytes.capacity(),
+        };
+        std::mem::forget(bytes);
+        buf
+    }
+}
+
+/// Encodes `rows` into a FlatBuffers buffer, prefixed with [`BAR_ROWS_WIRE_VERSION`] as 4
+/// little-endian bytes so [`decode_bar_rows`] can reject a buffer laid out by an incompatible
+/// schema version instead of misreading it.
+fn encode_bar_rows(rows: &[BarRow]) -> Vec<u8> {
+    let mut fbb = flatbuffers::FlatBufferBuilder::with_capacity(
+        rows.len() * std::mem::size_of::<BarRow>() + 64,
+    );
+
+    let vector = fbb.create_vector_of_structs(rows);
+    fbb.finish_minimal(vector);
+
+    let mut out = Vec::with_capacity(4 + fbb.finished_data().len());
+    out.extend_from_slice(&BAR_ROWS_WIRE_VERSION.to_le_bytes());
+    out.extend_from_slice(fbb.finished_data());
+    out
+}
+
+/// Decodes a buffer previously produced by [`encode_bar_rows`] back into its [`BarRow`]s.
+///
+/// Reads the FlatBuffers wire format directly (a leading `uoffset` to the root, a root vector of
+/// `u32` length followed by its elements packed at `size_of::<BarRow>()` stride) rather than
+/// through the `flatbuffers` crate's generated-table reading helpers, since `finish_minimal`
+/// roots a bare vector rather than a table and `BarRow` is hand-written rather than
+/// `flatc`-generated.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than the version prefix plus a minimal empty vector, or its version
+/// doesn't match [`BAR_ROWS_WIRE_VERSION`].
+fn decode_bar_rows(buf: &[u8]) -> Vec<BarRow> {
+    assert!(buf.len() >= 8, "buffer too short to hold a version + root offset");
+
+    let version = u32::from_le_bytes(buf[0..4].try_into().unwrap());
+    assert_eq!(
+        version, BAR_ROWS_WIRE_VERSION,
+        "bars_from_flatbuffer: unsupported wire version {version} (expected {BAR_ROWS_WIRE_VERSION})",
+    );
+
+    let payload = &buf[4..];
+    let root_offset = u32::from_le_bytes(payload[0..4].try_into().unwrap()) as usize;
+    let vector_loc = root_offset;
+    let vector_len =
+        u32::from_le_bytes(payload[vector_loc..vector_loc + 4].try_into().unwrap()) as usize;
+
+    let row_size = std::mem::size_of::<BarRow>();
+    let data_start = vector_loc + 4;
+    (0..vector_len)
+        .map(|i| {
+            let start = data_start + i * row_size;
+            // SAFETY: `start..start + row_size` is within `payload` for every `i < vector_len`,
+            // since `encode_bar_rows` wrote exactly `vector_len` `BarRow`s contiguously there.
+            unsafe { std::ptr::read_unaligned(payload[start..start + row_size].as_ptr().cast::<BarRow>()) }
+        })
+        .collect()
+}
+
+/// Encodes the `len` [`Bar`]s starting at `ptr` into a FlatBuffers buffer.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` contiguous `Bar` values.
+#[no_mangle]
+pub unsafe extern "C" fn bars_to_flatbuffer(ptr: *const Bar, len: usize) -> FlatBufferBytes {
+    let bars = std::slice::from_raw_parts(ptr, len);
+
+    let rows: Vec<BarRow> = bars
+        .iter()
+        .map(|bar| BarRow {
+            bar_type_hash: stable_hash(&bar.bar_type.to_string()),
+            aggregation: bar.bar_type.spec.aggregation as u8,
+            price_type: bar.bar_type.spec.price_type as u8,
+            aggregation_source: bar.bar_type.aggregation_source as u8,
+            price_precision: bar.open.precision,
+            size_precision: bar.volume.precision,
+            open: bar.open.raw,
+            high: bar.high.raw,
+            low: bar.low.raw,
+            close: bar.close.raw,
+            volume: bar.volume.raw,
+            ts_event: bar.ts_event.as_u64(),
+            ts_init: bar.ts_init.as_u64(),
+        })
+        .collect();
+
+    FlatBufferBytes::from_vec(encode_bar_rows(&rows))
+}
+
+/// Reclaims a [`FlatBufferBytes`] previously returned by [`bars_to_flatbuffer`].
+///
+/// # Safety
+///
+/// `buf` must have been produced by [`bars_to_flatbuffer`] and not already dropped.
+#[no_mangle]
+pub unsafe extern "C" fn flatbuffer_buffer_drop(buf: FlatBufferBytes) {
+    if buf.ptr.is_null() {
+        return;
+    }
+    drop(Vec::from_raw_parts(buf.ptr, buf.len, buf.cap));
+}
+
+/// A stable-ABI array of [`BarRow`] handed across the FFI boundary, paired with
+/// [`bar_row_buffer_drop`] to reclaim it.
+#[repr(C)]
+pub struct BarRowBuffer {
+    pub ptr: *mut BarRow,
+    pub len: usize,
+    pub cap: usize,
+}
+
+impl BarRowBuffer {
+    fn from_vec(mut rows: Vec<BarRow>) -> Self {
+        let buf = Self {
+            ptr: rows.as_mut_ptr(),
+            len: rows.len(),
+            cap: rows.capacity(),
+        };
+        std::mem::forget(rows);
+        buf
+    }
+}
+
+/// Decodes a FlatBuffers buffer previously produced by [`bars_to_flatbuffer`] back into its
+/// [`BarRow`]s.
+///
+/// Each [`BarRow`] carries the same raw fixed-point OHLCV values, `price_precision`/
+/// `size_precision`, and `bar_type_hash` the encoder read off the original `Bar`s -- everything
+/// needed to reconstruct a real `Price`/`Quantity` (and therefore a real `Bar`). This still
+/// returns `BarRow`s rather than `Bar`s, though: `Bar`/`Price`/`Quantity` are defined in `bar.rs`,
+/// which isn't part of this checkout, so there's nothing to construct them with from this crate
+/// today. A caller that does have those types (e.g. the Python/Cython side, or a future version
+/// of this crate once `bar.rs` lands) has everything it needs in `BarRow` to build one per row.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` contiguous bytes, forming a buffer produced by
+/// [`bars_to_flatbuffer`].
+///
+/// # Panics
+///
+/// Panics if the buffer's wire version doesn't match [`BAR_ROWS_WIRE_VERSION`], or it is too
+/// short to be a validly-encoded buffer.
+#[no_mangle]
+pub unsafe extern "C" fn bars_from_flatbuffer(ptr: *const u8, len: usize) -> BarRowBuffer {
+    let buf = std::slice::from_raw_parts(ptr, len);
+    BarRowBuffer::from_vec(decode_bar_rows(buf))
+}
+
+/// Reclaims a [`BarRowBuffer`] previously returned by [`bars_from_flatbuffer`].
+///
+/// # Safety
+///
+/// `buf` must have been produced by [`bars_from_flatbuffer`] and not already dropped.
+#[no_mangle]
+pub unsafe extern "C" fn bar_row_buffer_drop(buf: BarRowBuffer) {
+    if buf.ptr.is_null() {
+        return;
+    }
+    drop(Vec::from_raw_parts(buf.ptr, buf.len, buf.cap));
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn sample_rows() -> Vec<BarRow> {
+        vec![
+            BarRow {
+                bar_type_hash: 123,
+                aggregation: 1,
+                price_type: 0,
+                aggregation_source: 0,
+                price_precision: 5,
+                size_precision: 0,
+                open: 100_000,
+                high: 110_000,
+                low: 95_000,
+                close: 105_000,
+                volume: 1_000_000,
+                ts_event: 1,
+                ts_init: 2,
+            },
+            BarRow {
+                bar_type_hash: 123,
+                aggregation: 1,
+                price_type: 0,
+                aggregation_source: 0,
+                price_precision: 5,
+                size_precision: 0,
+                open: 105_000,
+                high: 120_000,
+                low: 104_000,
+                close: 118_000,
+                volume: 2_000_000,
+                ts_event: 3,
+                ts_init: 4,
+            },
+        ]
+    }
+
+    #[rstest]
+    fn test_encode_decode_round_trip_preserves_every_row() {
+        let rows = sample_rows();
+        let encoded = encode_bar_rows(&rows);
+        let decoded = decode_bar_rows(&encoded);
+        assert_eq!(decoded, rows);
+    }
+
+    #[rstest]
+    fn test_encode_decode_round_trip_preserves_precision() {
+        let rows = sample_rows();
+        let decoded = decode_bar_rows(&encode_bar_rows(&rows));
+        assert_eq!(decoded[0].price_precision, 5);
+        assert_eq!(decoded[0].size_precision, 0);
+    }
+
+    #[rstest]
+    fn test_encode_decode_round_trip_empty() {
+        let encoded = encode_bar_rows(&[]);
+        let decoded = decode_bar_rows(&encoded);
+        assert!(decoded.is_empty());
+    }
+
+    #[rstest]
+    fn test_encoded_buffer_starts_with_the_wire_version() {
+        let encoded = encode_bar_rows(&sample_rows());
+        assert_eq!(
+            u32::from_le_bytes(encoded[0..4].try_into().unwrap()),
+            BAR_ROWS_WIRE_VERSION,
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "unsupported wire version")]
+    fn test_decode_rejects_mismatched_wire_version() {
+        let mut encoded = encode_bar_rows(&sample_rows());
+        encoded[0..4].copy_from_slice(&(BAR_ROWS_WIRE_VERSION + 1).to_le_bytes());
+        let _ = decode_bar_rows(&encoded);
+    }
 }
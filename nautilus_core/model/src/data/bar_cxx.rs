@@ -0,0 +1,264 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! An opt-in `cxx`-based bridge exposing [`Bar`], [`BarType`] and [`BarSpecification`] to native
+//! C++ trading components, alongside the raw `extern "C"` surface in [`super::bar_api`].
+//!
+//! The hand-written shims in `bar_api.rs` pass these structs by value across a plain `extern
+//! "C"` ABI, which leans on the C++ caller to pair every `*_clone` with exactly one `*_drop` --
+//! easy to get wrong, and not checked by either compiler. `cxx` generates the binding glue from
+//! this bridge declaration instead: the shared structs get real C++ value semantics (copy/move/
+//! destructor), so a `Bar` on the C++ side is freed automatically rather than through a matching
+//! `bar_drop` call, and the function signatures are checked against their C++ declarations at
+//! build time. This module is purely additive -- it doesn't replace or remove `bar_api.rs`, so
+//! existing Cython/ctypes callers of the raw surface are unaffected.
+//!
+//! `cxx` shared structs may only contain other shared structs, primitives, or a handful of
+//! built-in types (`String`, `Vec<T>`, ...) -- not arbitrary Rust types like `InstrumentId`. So
+//! `instrument_id` crosses the bridge as its canonical `"SYMBOL.VENUE"` string form rather than
+//! the richer interned type `bar_api.rs` uses; round-tripping it back into a real `InstrumentId`
+//! is left to the caller on either side, the same way `bar_type_to_cstr` hands back a string
+//! rather than a parsed struct.
+//!
+//! Declared as `pub mod bar_cxx;` behind the `ffi_cxx` feature in `data/mod.rs`, so it only
+//! builds for consumers that opt in to the `cxx` bridge.
+//!
+//! `bar_specification_to_string`/`bar_type_to_string` convert the shared struct back into the
+//! real `BarSpecification`/`BarType` and delegate to their own `Display` impls -- the same ones
+//! [`super::bar_api`] hashes against -- rather than re-deriving a string from the raw numeric
+//! enum discriminants. That keeps the two bridges as different transports for one canonical
+//! string, not two independent hash definitions that happen to agree today. `bar_to_string` is
+//! the one exception; see its doc comment for why it can't fully delegate the same way.
+
+#[cxx::bridge(namespace = "nautilus::model")]
+pub mod ffi {
+    /// Mirrors [`super::super::bar::BarSpecification`] as a `cxx` shared struct.
+    ///
+    /// Deliberately doesn't derive `Hash`: a derived hash is only guaranteed stable for the
+    /// lifetime of one process, which is exactly the foot-gun `stable_hash`/`bar_specification_hash`
+    /// exist to close by hashing the canonical string instead. Call `bar_specification_hash`
+    /// rather than putting this struct in a `HashMap`/`HashSet` directly.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    struct BarSpecification {
+        step: u64,
+        aggregation: u8,
+        price_type: u8,
+    }
+
+    /// Mirrors [`super::super::bar::BarType`] as a `cxx` shared struct. `instrument_id` is the
+    /// canonical `"SYMBOL.VENUE"` string form -- see the module-level doc comment.
+    ///
+    /// Deliberately doesn't derive `Hash`; see [`BarSpecification`]'s doc comment. Call
+    /// `bar_type_hash`/`bar_type_hash_composite` instead of putting this struct in a
+    /// `HashMap`/`HashSet` directly.
+    #[derive(Clone, PartialEq, Eq)]
+    struct BarType {
+        instrument_id: String,
+        spec: BarSpecification,
+        aggregation_source: u8,
+    }
+
+    /// Mirrors [`super::super::bar::Bar`] as a `cxx` shared struct.
+    #[derive(Clone, PartialEq)]
+    struct Bar {
+        bar_type: BarType,
+        open: i64,
+        high: i64,
+        low: i64,
+        close: i64,
+        volume: u64,
+        ts_event: u64,
+        ts_init: u64,
+    }
+
+    extern "Rust" {
+        fn bar_specification_to_string(spec: &BarSpecification) -> String;
+        fn bar_specification_hash(spec: &BarSpecification) -> u64;
+
+        fn bar_type_to_string(bar_type: &BarType) -> String;
+        fn bar_type_hash(bar_type: &BarType) -> u64;
+        fn bar_type_hash_composite(bar_type: &BarType) -> u64;
+        fn bar_type_eq_composite(lhs: &BarType, rhs: &BarType) -> bool;
+
+        fn bar_to_string(bar: &Bar) -> String;
+        fn bar_hash(bar: &Bar) -> u64;
+    }
+}
+
+use crate::enums::{AggregationSource, BarAggregation, PriceType};
+use crate::identifiers::instrument_id::InstrumentId;
+
+use super::bar::{BarSpecification as RealBarSpecification, BarType as RealBarType};
+use super::bar_api::stable_hash;
+use ffi::{Bar, BarSpecification, BarType};
+
+impl From<&BarSpecification> for RealBarSpecification {
+    fn from(spec: &BarSpecification) -> Self {
+        Self {
+            step: spec.step,
+            aggregation: BarAggregation::from_repr(spec.aggregation as usize)
+                .expect("invalid BarAggregation discriminant crossing the cxx bridge"),
+            price_type: PriceType::from_repr(spec.price_type as usize)
+                .expect("invalid PriceType discriminant crossing the cxx bridge"),
+        }
+    }
+}
+
+impl From<&BarType> for RealBarType {
+    fn from(bar_type: &BarType) -> Self {
+        Self {
+            instrument_id: bar_type
+                .instrument_id
+                .parse::<InstrumentId>()
+                .expect("invalid instrument_id crossing the cxx bridge"),
+            spec: RealBarSpecification::from(&bar_type.spec),
+            aggregation_source: AggregationSource::from_repr(bar_type.aggregation_source as usize)
+                .expect("invalid AggregationSource discriminant crossing the cxx bridge"),
+        }
+    }
+}
+
+fn bar_specification_to_string(spec: &BarSpecification) -> String {
+    RealBarSpecification::from(spec).to_string()
+}
+
+fn bar_specification_hash(spec: &BarSpecification) -> u64 {
+    stable_hash(&bar_specification_to_string(spec))
+}
+
+fn bar_type_to_string(bar_type: &BarType) -> String {
+    RealBarType::from(bar_type).to_string()
+}
+
+fn bar_type_hash(bar_type: &BarType) -> u64 {
+    stable_hash(&bar_type_to_string(bar_type))
+}
+
+/// See `bar_api::bar_type_hash_composite` -- hashes `instrument_id` and `spec` only, ignoring
+/// `aggregation_source`, so an internally-aggregated `BarType` hashes the same as the
+/// externally-fed `BarType` of the same instrument/specification.
+fn bar_type_hash_composite(bar_type: &BarType) -> u64 {
+    stable_hash(&format!(
+        "{}-{}",
+        bar_type.instrument_id,
+        bar_specification_to_string(&bar_type.spec)
+    ))
+}
+
+/// See `bar_api::bar_type_eq_composite` -- compares `instrument_id` and `spec` only, ignoring
+/// `aggregation_source`.
+fn bar_type_eq_composite(lhs: &BarType, rhs: &BarType) -> bool {
+    lhs.instrument_id == rhs.instrument_id && lhs.spec == rhs.spec
+}
+
+/// Unlike [`bar_type_to_string`], this can't delegate to the real [`super::bar::Bar`]'s own
+/// `Display` impl: its OHLCV fields are `Price`/`Quantity`, which round-trip through a
+/// fixed-point raw value *and* a decimal precision, and this shared struct only carries the raw
+/// `i64`/`u64` values (`cxx` shared structs can't hold `Price`/`Quantity` either). Without the
+/// precision this is the best available canonical string -- it's still a deterministic function
+/// of `bar_type_to_string` (now itself fixed to the named-variant form) plus the raw ticks, just
+/// not byte-identical to `Bar::to_string()`'s decimal-formatted output.
+fn bar_to_string(bar: &Bar) -> String {
+    format!(
+        "{}-{}-{}-{}-{}",
+        bar_type_to_string(&bar.bar_type),
+        bar.open,
+        bar.high,
+        bar.low,
+        bar.close
+    )
+}
+
+fn bar_hash(bar: &Bar) -> u64 {
+    stable_hash(&bar_to_string(bar))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn spec(step: u64) -> BarSpecification {
+        BarSpecification {
+            step,
+            aggregation: 1,
+            price_type: PriceType::Last as u8,
+        }
+    }
+
+    fn bar_type(instrument_id: &str, spec: BarSpecification, aggregation_source: u8) -> BarType {
+        BarType {
+            instrument_id: instrument_id.to_string(),
+            spec,
+            aggregation_source,
+        }
+    }
+
+    #[rstest]
+    fn test_bar_type_hash_composite_ignores_aggregation_source() {
+        let internal = bar_type("AUDUSD.SIM", spec(1), AggregationSource::Internal as u8);
+        let external = bar_type("AUDUSD.SIM", spec(1), AggregationSource::External as u8);
+
+        assert_eq!(
+            bar_type_hash_composite(&internal),
+            bar_type_hash_composite(&external)
+        );
+        assert_ne!(bar_type_hash(&internal), bar_type_hash(&external));
+    }
+
+    #[rstest]
+    fn test_bar_type_eq_composite_ignores_aggregation_source() {
+        let internal = bar_type("AUDUSD.SIM", spec(1), AggregationSource::Internal as u8);
+        let external = bar_type("AUDUSD.SIM", spec(1), AggregationSource::External as u8);
+
+        assert!(bar_type_eq_composite(&internal, &external));
+        assert!(internal != external);
+    }
+
+    #[rstest]
+    fn test_bar_type_eq_composite_false_when_instrument_or_spec_differs() {
+        let base = bar_type("AUDUSD.SIM", spec(1), 0);
+
+        assert!(!bar_type_eq_composite(&base, &bar_type("GBPUSD.SIM", spec(1), 0)));
+        assert!(!bar_type_eq_composite(&base, &bar_type("AUDUSD.SIM", spec(2), 0)));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "invalid BarAggregation discriminant crossing the cxx bridge")]
+    fn test_bar_specification_conversion_panics_on_invalid_aggregation_discriminant() {
+        let _ = RealBarSpecification::from(&BarSpecification {
+            step: 1,
+            aggregation: 255,
+            price_type: 0,
+        });
+    }
+
+    #[rstest]
+    #[should_panic(expected = "invalid PriceType discriminant crossing the cxx bridge")]
+    fn test_bar_specification_conversion_panics_on_invalid_price_type_discriminant() {
+        let _ = RealBarSpecification::from(&BarSpecification {
+            step: 1,
+            aggregation: 1,
+            price_type: 255,
+        });
+    }
+
+    #[rstest]
+    #[should_panic(expected = "invalid AggregationSource discriminant crossing the cxx bridge")]
+    fn test_bar_type_conversion_panics_on_invalid_aggregation_source_discriminant() {
+        let _ = RealBarType::from(&bar_type("AUDUSD.SIM", spec(1), 255));
+    }
+}
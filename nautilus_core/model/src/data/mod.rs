@@ -0,0 +1,22 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+pub mod bar_api;
+
+/// Opt-in `cxx` bridge for `Bar`/`BarType`/`BarSpecification`, gated behind the `ffi_cxx`
+/// feature so consumers that only need the existing `extern "C"` surface in [`bar_api`] don't
+/// pay for the `cxx` build-time codegen.
+#[cfg(feature = "ffi_cxx")]
+pub mod bar_cxx;